@@ -10,6 +10,8 @@ const SYSTEM_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
 pub struct App {
     #[serde(skip)]
     system: Arc<RwLock<sysinfo::System>>,
+    #[serde(skip)]
+    proc_cache: Arc<RwLock<processes::ProcCache>>,
     user_input: Arc<RwLock<processes::UserInput>>,
 }
 
@@ -17,6 +19,7 @@ impl Default for App {
     fn default() -> Self {
         Self {
             system: Arc::new(RwLock::new(sysinfo::System::new_all())),
+            proc_cache: Arc::new(RwLock::new(processes::ProcCache::default())),
             user_input: Arc::new(RwLock::new(processes::UserInput::default())),
         }
     }
@@ -30,10 +33,19 @@ impl App {
             Default::default()
         };
 
+        let read_memory_breakdown = app
+            .user_input
+            .read()
+            .is_ok_and(|user_input| user_input.shows_memory_breakdown());
+        if let (Ok(system), Ok(mut proc_cache)) = (app.system.read(), app.proc_cache.write()) {
+            proc_cache.refresh(&system, read_memory_breakdown);
+        }
+
         let system = app.system.clone();
+        let proc_cache = app.proc_cache.clone();
         let user_input = app.user_input.clone();
         let ctx = cc.egui_ctx.clone();
-        thread::spawn(move || Self::system_refresh_loop(&system, &user_input, &ctx));
+        thread::spawn(move || Self::system_refresh_loop(&system, &proc_cache, &user_input, &ctx));
 
         app
     }
@@ -42,12 +54,17 @@ impl App {
         self.system.clone()
     }
 
+    pub(crate) fn proc_cache(&self) -> Arc<RwLock<processes::ProcCache>> {
+        self.proc_cache.clone()
+    }
+
     pub(crate) fn user_input(&self) -> Arc<RwLock<processes::UserInput>> {
         self.user_input.clone()
     }
 
     fn system_refresh_loop(
         system: &Arc<RwLock<sysinfo::System>>,
+        proc_cache: &Arc<RwLock<processes::ProcCache>>,
         user_input: &Arc<RwLock<processes::UserInput>>,
         ctx: &egui::Context,
     ) -> ! {
@@ -57,9 +74,33 @@ impl App {
                 .map(|user_input| user_input.continue_refreshing())
                 .unwrap_or(false);
 
+            let read_memory_breakdown = user_input
+                .read()
+                .is_ok_and(|user_input| user_input.shows_memory_breakdown());
+
+            let watched_pids = user_input
+                .read()
+                .map(|user_input| user_input.watched_pids())
+                .unwrap_or_default();
+
             if should_refresh {
-                if let Ok(mut system) = system.write() {
-                    system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+                if let (Ok(mut system), Ok(mut proc_cache)) = (system.write(), proc_cache.write()) {
+                    processes::refresh_processes(
+                        &mut system,
+                        &mut proc_cache,
+                        read_memory_breakdown,
+                    );
+                }
+            } else if !watched_pids.is_empty()
+                && let Ok(mut system) = system.write()
+            {
+                system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&watched_pids), true);
+            }
+
+            if should_refresh || !watched_pids.is_empty() {
+                if let (Ok(system), Ok(mut user_input)) = (system.read(), user_input.write()) {
+                    user_input.retain_running_collapsed(&system);
+                    user_input.check_terminations(&system);
                 }
                 ctx.request_repaint();
             }
@@ -0,0 +1,32 @@
+mod priority;
+mod protection;
+mod scheduling;
+mod signals;
+mod targets;
+mod termination;
+
+pub use priority::NICE_RANGE;
+pub use priority::renice;
+pub use protection::ProtectedProcesses;
+pub use protection::ProtectionMode;
+pub use protection::is_destructive;
+pub use protection::is_harmless;
+pub use protection::is_suspending;
+pub use scheduling::IO_LEVEL_RANGE;
+pub use scheduling::IoClass;
+pub use scheduling::IoPriority;
+pub use scheduling::cpu_affinity;
+pub use scheduling::io_priority;
+pub use scheduling::set_cpu_affinity;
+pub use scheduling::set_io_priority;
+pub use signals::SignalReport;
+pub use signals::send_signal_to_all;
+pub use signals::signal_description;
+pub use signals::signal_from_name;
+pub use signals::signal_name;
+pub use signals::supported_signals;
+pub use targets::signal_targets;
+pub use termination::Escalation;
+pub use termination::Termination;
+pub use termination::TreeSignalOrder;
+pub use termination::check_terminations;
@@ -0,0 +1,101 @@
+#[cfg(target_os = "linux")]
+use crate::processes::control::scheduling;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+pub const NICE_RANGE: RangeInclusive<i32> = -20..=19;
+
+const NICE_PERMISSION_HINT: &str =
+    "raising priority or changing other users' processes requires root or CAP_SYS_NICE";
+
+pub enum PriorityError {
+    NoSuchProcess,
+    PermissionDenied(&'static str),
+    #[cfg(not(target_os = "linux"))]
+    Unsupported,
+    #[cfg(target_os = "linux")]
+    PartiallyApplied {
+        applied: usize,
+        total: usize,
+        error: Box<Self>,
+    },
+    Failed(String),
+}
+
+impl fmt::Display for PriorityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSuchProcess => f.write_str("no such process"),
+            Self::PermissionDenied(hint) => write!(f, "permission denied; {hint}"),
+            #[cfg(not(target_os = "linux"))]
+            Self::Unsupported => f.write_str("not supported on this platform"),
+            #[cfg(target_os = "linux")]
+            Self::PartiallyApplied {
+                applied,
+                total,
+                error,
+            } => write!(f, "changed {applied} of {total} threads; {error}"),
+            Self::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl From<rustix::io::Errno> for PriorityError {
+    fn from(errno: rustix::io::Errno) -> Self {
+        match errno {
+            rustix::io::Errno::SRCH => Self::NoSuchProcess,
+            rustix::io::Errno::ACCESS | rustix::io::Errno::PERM => {
+                Self::PermissionDenied(NICE_PERMISSION_HINT)
+            }
+            errno => Self::Failed(errno.to_string()),
+        }
+    }
+}
+
+impl PriorityError {
+    #[cfg(target_os = "linux")]
+    pub(super) fn with_permission_hint(self, hint: &'static str) -> Self {
+        match self {
+            Self::PermissionDenied(_) => Self::PermissionDenied(hint),
+            Self::PartiallyApplied {
+                applied,
+                total,
+                error,
+            } => Self::PartiallyApplied {
+                applied,
+                total,
+                error: Box::new(error.with_permission_hint(hint)),
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn renice_task(tid: u32, nice: i32) -> Result<(), PriorityError> {
+    let tid = i32::try_from(tid)
+        .ok()
+        .and_then(rustix::process::Pid::from_raw)
+        .ok_or(PriorityError::NoSuchProcess)?;
+    rustix::process::setpriority_process(
+        Some(tid),
+        nice.clamp(*NICE_RANGE.start(), *NICE_RANGE.end()),
+    )
+    .map_err(PriorityError::from)
+}
+
+#[cfg(target_os = "linux")]
+pub fn renice(pid: u32, nice: i32) -> Result<(), PriorityError> {
+    scheduling::for_each_task(pid, |tid| renice_task(tid, nice))
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn renice(pid: u32, nice: i32) -> Result<(), PriorityError> {
+    renice_task(pid, nice)
+}
+
+#[cfg(not(unix))]
+pub fn renice(_pid: u32, _nice: i32) -> Result<(), PriorityError> {
+    Err(PriorityError::Unsupported)
+}
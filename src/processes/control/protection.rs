@@ -0,0 +1,180 @@
+use crate::processes::data;
+
+const INIT_PID: u32 = 1;
+const DISPLAY_SERVER_NAMES: [&str; 10] = [
+    "Xorg",
+    "X",
+    "Xwayland",
+    "gnome-shell",
+    "kwin_wayland",
+    "kwin_x11",
+    "mutter",
+    "sway",
+    "weston",
+    "Hyprland",
+];
+
+const HARMLESS_SIGNALS: [sysinfo::Signal; 4] = [
+    sysinfo::Signal::Continue,
+    sysinfo::Signal::Child,
+    sysinfo::Signal::Urgent,
+    sysinfo::Signal::Winch,
+];
+const SUSPENDING_SIGNALS: [sysinfo::Signal; 4] = [
+    sysinfo::Signal::Stop,
+    sysinfo::Signal::TSTP,
+    sysinfo::Signal::TTIN,
+    sysinfo::Signal::TTOU,
+];
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProtectionMode {
+    #[default]
+    Refuse,
+    ExtraConfirmation,
+}
+
+impl ProtectionMode {
+    pub const ALL: [Self; 2] = [Self::Refuse, Self::ExtraConfirmation];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Refuse => "Refuse",
+            Self::ExtraConfirmation => "Require Extra Confirmation",
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct ProtectedProcesses {
+    pub(crate) protect_init: bool,
+    pub(crate) protect_display_server: bool,
+    pub(crate) protect_self: bool,
+    pub(crate) name_patterns: String,
+    pub(crate) mode: ProtectionMode,
+}
+
+impl Default for ProtectedProcesses {
+    fn default() -> Self {
+        Self {
+            protect_init: true,
+            protect_display_server: true,
+            protect_self: true,
+            name_patterns: String::new(),
+            mode: ProtectionMode::default(),
+        }
+    }
+}
+
+impl ProtectedProcesses {
+    pub fn protection_reason(&self, process: &sysinfo::Process) -> Option<&'static str> {
+        let pid = process.pid().as_u32();
+        let name = data::extract_name(process).unwrap_or_default();
+
+        if self.protect_init && pid == INIT_PID {
+            Some("init process")
+        } else if self.protect_self && pid == std::process::id() {
+            Some("this application")
+        } else if self.protect_display_server && DISPLAY_SERVER_NAMES.contains(&name) {
+            Some("display server")
+        } else if self
+            .name_patterns
+            .lines()
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .any(|pattern| wildcard_match(pattern, name))
+        {
+            Some("matches a protected name pattern")
+        } else {
+            None
+        }
+    }
+
+    pub fn protected_processes(
+        &self,
+        system: &sysinfo::System,
+        pids: &[u32],
+    ) -> Vec<(u32, &'static str)> {
+        pids.iter()
+            .filter_map(|&pid| system.process(sysinfo::Pid::from_u32(pid)))
+            .filter_map(|process| {
+                self.protection_reason(process)
+                    .map(|reason| (process.pid().as_u32(), reason))
+            })
+            .collect()
+    }
+}
+
+pub fn is_destructive(signal: sysinfo::Signal) -> bool {
+    !is_harmless(signal) && !is_suspending(signal)
+}
+
+pub fn is_harmless(signal: sysinfo::Signal) -> bool {
+    HARMLESS_SIGNALS.contains(&signal)
+}
+
+pub fn is_suspending(signal: sysinfo::Signal) -> bool {
+    SUSPENDING_SIGNALS.contains(&signal)
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        let Some(index) = remaining.find(part) else {
+            return false;
+        };
+        remaining = remaining.get(index + part.len()..).unwrap_or_default();
+    }
+    remaining.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wildcard_match;
+
+    #[test]
+    fn matches_without_wildcards() {
+        assert!(wildcard_match("systemd", "systemd"));
+        assert!(!wildcard_match("systemd", "systemd-journald"));
+        assert!(!wildcard_match("systemd", "system"));
+    }
+
+    #[test]
+    fn matches_leading_wildcard() {
+        assert!(wildcard_match("*d", "sshd"));
+        assert!(wildcard_match("*sshd", "sshd"));
+        assert!(!wildcard_match("*d", "bash"));
+    }
+
+    #[test]
+    fn matches_trailing_wildcard() {
+        assert!(wildcard_match("systemd*", "systemd-logind"));
+        assert!(wildcard_match("systemd*", "systemd"));
+        assert!(!wildcard_match("systemd*", "init"));
+    }
+
+    #[test]
+    fn matches_middle_wildcard() {
+        assert!(wildcard_match("kworker*events", "kworker/0:1-events"));
+        assert!(wildcard_match("a*b*c", "aXbYc"));
+        assert!(!wildcard_match("a*b*c", "aXcYb"));
+        assert!(!wildcard_match("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn matches_empty_pattern_only_against_empty_text() {
+        assert!(wildcard_match("", ""));
+        assert!(!wildcard_match("", "bash"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("*", "bash"));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::processes::control::priority::PriorityError;
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+pub const IO_LEVEL_RANGE: RangeInclusive<u8> = 0..=7;
+
+#[cfg(target_os = "linux")]
+const OTHER_USERS_PERMISSION_HINT: &str =
+    "changing other users' processes requires root or CAP_SYS_NICE";
+#[cfg(target_os = "linux")]
+const REAL_TIME_IO_PERMISSION_HINT: &str = "the real-time I/O class requires root or CAP_SYS_ADMIN";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum IoClass {
+    None,
+    RealTime,
+    BestEffort,
+    Idle,
+}
+
+impl IoClass {
+    pub const ALL: [Self; 4] = [Self::None, Self::RealTime, Self::BestEffort, Self::Idle];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::RealTime => "Realtime",
+            Self::BestEffort => "Best Effort",
+            Self::Idle => "Idle",
+        }
+    }
+
+    pub fn has_level(self) -> bool {
+        matches!(self, Self::RealTime | Self::BestEffort)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct IoPriority {
+    pub class: IoClass,
+    pub level: u8,
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{IoClass, IoPriority, OTHER_USERS_PERMISSION_HINT};
+    use crate::processes::control::priority::PriorityError;
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::process::Command;
+
+    impl IoClass {
+        fn from_name(name: &str) -> Self {
+            match name {
+                "realtime" => Self::RealTime,
+                "best-effort" => Self::BestEffort,
+                "idle" => Self::Idle,
+                _ => Self::None,
+            }
+        }
+
+        fn number(self) -> u8 {
+            match self {
+                Self::None => 0,
+                Self::RealTime => 1,
+                Self::BestEffort => 2,
+                Self::Idle => 3,
+            }
+        }
+    }
+
+    fn ionice(arguments: &[String]) -> Result<String, PriorityError> {
+        let output = Command::new("ionice")
+            .args(arguments)
+            .env("LC_ALL", "C")
+            .output()
+            .map_err(|error| PriorityError::Failed(format!("failed to run ionice: {error}")))?;
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("No such process") {
+            Err(PriorityError::NoSuchProcess)
+        } else if stderr.contains("Operation not permitted") || stderr.contains("Permission denied")
+        {
+            Err(PriorityError::PermissionDenied(OTHER_USERS_PERMISSION_HINT))
+        } else {
+            Err(PriorityError::Failed(stderr.trim().to_owned()))
+        }
+    }
+
+    pub(super) fn task_ids(pid: u32) -> Vec<u32> {
+        let Ok(entries) = fs::read_dir(format!("/proc/{pid}/task")) else {
+            return vec![pid];
+        };
+
+        let task_ids: Vec<u32> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+            .collect();
+        if task_ids.is_empty() {
+            vec![pid]
+        } else {
+            task_ids
+        }
+    }
+
+    fn rustix_pid(tid: u32) -> Result<rustix::process::Pid, PriorityError> {
+        i32::try_from(tid)
+            .ok()
+            .and_then(rustix::process::Pid::from_raw)
+            .ok_or(PriorityError::NoSuchProcess)
+    }
+
+    pub(super) fn io_priority(pid: u32) -> Result<IoPriority, PriorityError> {
+        let output = ionice(&["-p".to_owned(), pid.to_string()])?;
+        let (class, level) = output
+            .trim()
+            .split_once(": prio ")
+            .unwrap_or((output.trim(), "0"));
+        Ok(IoPriority {
+            class: IoClass::from_name(class),
+            level: level.parse().unwrap_or_default(),
+        })
+    }
+
+    pub(super) fn set_io_priority(tid: u32, priority: IoPriority) -> Result<(), PriorityError> {
+        let mut arguments = vec!["-c".to_owned(), priority.class.number().to_string()];
+        if priority.class.has_level() {
+            arguments.extend(["-n".to_owned(), priority.level.to_string()]);
+        }
+        arguments.extend(["-p".to_owned(), tid.to_string()]);
+        ionice(&arguments).map(|_| ())
+    }
+
+    pub(super) fn cpu_affinity(
+        pid: u32,
+        cpu_count: usize,
+    ) -> Result<BTreeSet<usize>, PriorityError> {
+        let cpu_set = rustix::thread::sched_getaffinity(Some(rustix_pid(pid)?))?;
+        Ok((0..cpu_count).filter(|&cpu| cpu_set.is_set(cpu)).collect())
+    }
+
+    pub(super) fn set_cpu_affinity(tid: u32, cpus: &BTreeSet<usize>) -> Result<(), PriorityError> {
+        let mut cpu_set = rustix::thread::CpuSet::new();
+        for &cpu in cpus {
+            if cpu < rustix::thread::CpuSet::MAX_CPU {
+                cpu_set.set(cpu);
+            }
+        }
+        rustix::thread::sched_setaffinity(Some(rustix_pid(tid)?), &cpu_set)
+            .map_err(PriorityError::from)
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(super) fn for_each_task(
+    pid: u32,
+    apply: impl Fn(u32) -> Result<(), PriorityError>,
+) -> Result<(), PriorityError> {
+    let mut total = 0;
+    let mut applied = 0;
+    let mut first_error = None;
+    for tid in linux::task_ids(pid) {
+        total += 1;
+        match apply(tid) {
+            Ok(()) => applied += 1,
+            Err(PriorityError::NoSuchProcess) if tid != pid => total -= 1,
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    match first_error {
+        None => Ok(()),
+        Some(error) if applied == 0 => Err(error),
+        Some(error) => Err(PriorityError::PartiallyApplied {
+            applied,
+            total,
+            error: Box::new(error),
+        }),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn io_priority(pid: u32) -> Result<IoPriority, PriorityError> {
+    linux::io_priority(pid).map_err(|error| error.with_permission_hint(OTHER_USERS_PERMISSION_HINT))
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_io_priority(pid: u32, priority: IoPriority) -> Result<(), PriorityError> {
+    let hint = if priority.class == IoClass::RealTime {
+        REAL_TIME_IO_PERMISSION_HINT
+    } else {
+        OTHER_USERS_PERMISSION_HINT
+    };
+    for_each_task(pid, |tid| linux::set_io_priority(tid, priority))
+        .map_err(|error| error.with_permission_hint(hint))
+}
+
+#[cfg(target_os = "linux")]
+pub fn cpu_affinity(pid: u32, cpu_count: usize) -> Result<BTreeSet<usize>, PriorityError> {
+    linux::cpu_affinity(pid, cpu_count)
+        .map_err(|error| error.with_permission_hint(OTHER_USERS_PERMISSION_HINT))
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_cpu_affinity(pid: u32, cpus: &BTreeSet<usize>) -> Result<(), PriorityError> {
+    if cpus.is_empty() {
+        return Err(PriorityError::Failed(
+            "at least one CPU must be selected".to_owned(),
+        ));
+    }
+    for_each_task(pid, |tid| linux::set_cpu_affinity(tid, cpus))
+        .map_err(|error| error.with_permission_hint(OTHER_USERS_PERMISSION_HINT))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn io_priority(_pid: u32) -> Result<IoPriority, PriorityError> {
+    Err(PriorityError::Unsupported)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_io_priority(_pid: u32, _priority: IoPriority) -> Result<(), PriorityError> {
+    Err(PriorityError::Unsupported)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn cpu_affinity(_pid: u32, _cpu_count: usize) -> Result<BTreeSet<usize>, PriorityError> {
+    Err(PriorityError::Unsupported)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_cpu_affinity(_pid: u32, _cpus: &BTreeSet<usize>) -> Result<(), PriorityError> {
+    Err(PriorityError::Unsupported)
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+
+const SIGNAL_NAMES: [(sysinfo::Signal, &str, &str); 32] = [
+    (sysinfo::Signal::Hangup, "SIGHUP", "Hang Up"),
+    (sysinfo::Signal::Interrupt, "SIGINT", "Interrupt"),
+    (sysinfo::Signal::Quit, "SIGQUIT", "Quit"),
+    (sysinfo::Signal::Illegal, "SIGILL", "Illegal Instruction"),
+    (sysinfo::Signal::Trap, "SIGTRAP", "Trap"),
+    (sysinfo::Signal::Abort, "SIGABRT", "Abort"),
+    (sysinfo::Signal::IOT, "SIGIOT", "IOT Trap"),
+    (sysinfo::Signal::Bus, "SIGBUS", "Bus Error"),
+    (
+        sysinfo::Signal::FloatingPointException,
+        "SIGFPE",
+        "Floating Point Exception",
+    ),
+    (sysinfo::Signal::Kill, "SIGKILL", "Kill"),
+    (sysinfo::Signal::User1, "SIGUSR1", "User Signal 1"),
+    (sysinfo::Signal::Segv, "SIGSEGV", "Segmentation Fault"),
+    (sysinfo::Signal::User2, "SIGUSR2", "User Signal 2"),
+    (sysinfo::Signal::Pipe, "SIGPIPE", "Broken Pipe"),
+    (sysinfo::Signal::Alarm, "SIGALRM", "Alarm"),
+    (sysinfo::Signal::Term, "SIGTERM", "Terminate"),
+    (sysinfo::Signal::Child, "SIGCHLD", "Child Status Changed"),
+    (sysinfo::Signal::Continue, "SIGCONT", "Continue"),
+    (sysinfo::Signal::Stop, "SIGSTOP", "Pause"),
+    (sysinfo::Signal::TSTP, "SIGTSTP", "Terminal Stop"),
+    (sysinfo::Signal::TTIN, "SIGTTIN", "Terminal Input"),
+    (sysinfo::Signal::TTOU, "SIGTTOU", "Terminal Output"),
+    (sysinfo::Signal::Urgent, "SIGURG", "Urgent Socket Data"),
+    (sysinfo::Signal::XCPU, "SIGXCPU", "CPU Time Limit Exceeded"),
+    (sysinfo::Signal::XFSZ, "SIGXFSZ", "File Size Limit Exceeded"),
+    (sysinfo::Signal::VirtualAlarm, "SIGVTALRM", "Virtual Alarm"),
+    (sysinfo::Signal::Profiling, "SIGPROF", "Profiling Timer"),
+    (sysinfo::Signal::Winch, "SIGWINCH", "Window Resized"),
+    (sysinfo::Signal::IO, "SIGIO", "I/O Possible"),
+    (sysinfo::Signal::Poll, "SIGPOLL", "Pollable Event"),
+    (sysinfo::Signal::Power, "SIGPWR", "Power Failure"),
+    (sysinfo::Signal::Sys, "SIGSYS", "Bad System Call"),
+];
+
+pub enum SignalError {
+    NoSuchProcess,
+    Unsupported,
+    Failed,
+}
+
+impl fmt::Display for SignalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSuchProcess => f.write_str("no such process"),
+            Self::Unsupported => f.write_str("signal not supported on this platform"),
+            Self::Failed => f.write_str("failed, possibly due to insufficient permissions"),
+        }
+    }
+}
+
+pub fn supported_signals() -> impl Iterator<Item = sysinfo::Signal> {
+    sysinfo::SUPPORTED_SIGNALS.iter().copied()
+}
+
+pub fn signal_name(signal: sysinfo::Signal) -> &'static str {
+    SIGNAL_NAMES
+        .iter()
+        .find(|(known_signal, _, _)| *known_signal == signal)
+        .map_or("SIG?", |(_, name, _)| name)
+}
+
+pub fn signal_description(signal: sysinfo::Signal) -> &'static str {
+    SIGNAL_NAMES
+        .iter()
+        .find(|(known_signal, _, _)| *known_signal == signal)
+        .map_or("Unknown", |(_, _, description)| description)
+}
+
+pub fn signal_from_name(name: &str) -> Option<sysinfo::Signal> {
+    supported_signals().find(|&signal| signal_name(signal) == name)
+}
+
+pub fn send_signal(
+    system: &sysinfo::System,
+    pid: u32,
+    signal: sysinfo::Signal,
+) -> Result<(), SignalError> {
+    let process = system
+        .process(sysinfo::Pid::from_u32(pid))
+        .ok_or(SignalError::NoSuchProcess)?;
+
+    match process.kill_with(signal) {
+        Some(true) => Ok(()),
+        Some(false) => Err(SignalError::Failed),
+        None => Err(SignalError::Unsupported),
+    }
+}
+
+pub struct SignalReport {
+    signal: sysinfo::Signal,
+    succeeded: Vec<u32>,
+    failed: Vec<(u32, SignalError)>,
+}
+
+impl SignalReport {
+    pub fn extend(&mut self, other: Self) {
+        self.succeeded.extend(other.succeeded);
+        self.failed.extend(other.failed);
+    }
+
+    pub fn details(&self) -> Vec<String> {
+        let name = signal_name(self.signal);
+        let succeeded = self
+            .succeeded
+            .iter()
+            .map(|pid| format!("{pid}: sent {name}"));
+        let failed = self
+            .failed
+            .iter()
+            .map(|(pid, error)| format!("{pid}: {error}"));
+        succeeded.chain(failed).collect()
+    }
+}
+
+impl fmt::Display for SignalReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = signal_name(self.signal);
+        match (self.succeeded.as_slice(), self.failed.as_slice()) {
+            ([pid], []) => write!(f, "Sent {name} to {pid}"),
+            ([], [(pid, error)]) => write!(f, "Sending {name} to {pid} failed: {error}"),
+            (succeeded, []) => write!(f, "Sent {name} to {} processes", succeeded.len()),
+            (succeeded, failed) => {
+                write!(
+                    f,
+                    "Sent {name} to {} of {} processes; failed:",
+                    succeeded.len(),
+                    succeeded.len() + failed.len()
+                )?;
+                for (pid, error) in failed {
+                    write!(f, " {pid} ({error})")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn send_signal_to_all(
+    system: &sysinfo::System,
+    pids: &[u32],
+    signal: sysinfo::Signal,
+) -> SignalReport {
+    let mut report = SignalReport {
+        signal,
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for &pid in pids {
+        match send_signal(system, pid, signal) {
+            Ok(()) => report.succeeded.push(pid),
+            Err(error) => report.failed.push((pid, error)),
+        }
+    }
+
+    report
+}
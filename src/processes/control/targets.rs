@@ -0,0 +1,22 @@
+use crate::processes::data;
+use std::collections::HashSet;
+
+pub fn signal_targets(system: &sysinfo::System, pids: &[u32], include_subtree: bool) -> Vec<u32> {
+    if !include_subtree {
+        return pids.to_vec();
+    }
+
+    let process_tree = data::process_tree(system);
+    let mut seen = HashSet::new();
+    pids.iter()
+        .flat_map(|&pid| {
+            let subtree_pids = process_tree.subtree_pids(pid);
+            if subtree_pids.is_empty() {
+                vec![pid]
+            } else {
+                subtree_pids
+            }
+        })
+        .filter(|&pid| seen.insert(pid))
+        .collect()
+}
@@ -0,0 +1,245 @@
+use crate::processes::control::{signals, targets};
+use crate::processes::data;
+use std::time::{Duration, Instant};
+
+const KILL_SETTLE_PERIOD: Duration = Duration::from_secs(1);
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TreeSignalOrder {
+    #[default]
+    LeavesFirst,
+    ParentsFirst,
+    AllAtOnce,
+}
+
+impl TreeSignalOrder {
+    pub const ALL: [Self; 3] = [Self::LeavesFirst, Self::ParentsFirst, Self::AllAtOnce];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::LeavesFirst => "Leaves First",
+            Self::ParentsFirst => "Parents First",
+            Self::AllAtOnce => "All at Once",
+        }
+    }
+
+    fn order(self, mut pids: Vec<u32>) -> Vec<u32> {
+        if self == Self::ParentsFirst {
+            pids.reverse();
+        }
+        pids
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Escalation {
+    Never,
+    Automatic,
+    Confirm,
+}
+
+pub struct Termination {
+    root_pid: u32,
+    is_tree: bool,
+    keys: Vec<data::ProcessKey>,
+    deadline: Instant,
+    escalation: Escalation,
+    awaiting_confirmation: bool,
+}
+
+impl Termination {
+    pub fn start_tree(
+        system: &sysinfo::System,
+        root_pid: u32,
+        order: TreeSignalOrder,
+        signal: sysinfo::Signal,
+        grace_period: Duration,
+        escalation: Escalation,
+    ) -> (Self, signals::SignalReport) {
+        let pids = order.order(targets::signal_targets(system, &[root_pid], true));
+        if order != TreeSignalOrder::AllAtOnce {
+            return Self::start(
+                system,
+                root_pid,
+                true,
+                &pids,
+                signal,
+                grace_period,
+                escalation,
+            );
+        }
+
+        signals::send_signal_to_all(system, &pids, sysinfo::Signal::Stop);
+        let started = Self::start(
+            system,
+            root_pid,
+            true,
+            &pids,
+            signal,
+            grace_period,
+            escalation,
+        );
+        signals::send_signal_to_all(system, &pids, sysinfo::Signal::Continue);
+        started
+    }
+
+    pub fn start_stop(
+        system: &sysinfo::System,
+        pid: u32,
+        grace_period: Duration,
+        escalation: Escalation,
+    ) -> (Self, signals::SignalReport) {
+        Self::start(
+            system,
+            pid,
+            false,
+            &[pid],
+            sysinfo::Signal::Term,
+            grace_period,
+            escalation,
+        )
+    }
+
+    fn start(
+        system: &sysinfo::System,
+        root_pid: u32,
+        is_tree: bool,
+        pids: &[u32],
+        signal: sysinfo::Signal,
+        grace_period: Duration,
+        escalation: Escalation,
+    ) -> (Self, signals::SignalReport) {
+        let keys = pids
+            .iter()
+            .filter_map(|&pid| system.process(sysinfo::Pid::from_u32(pid)))
+            .map(data::ProcessKey::from_process)
+            .collect();
+        let report = signals::send_signal_to_all(system, pids, signal);
+
+        let escalation = if signal == sysinfo::Signal::Kill {
+            Escalation::Never
+        } else {
+            escalation
+        };
+        let termination = Self {
+            root_pid,
+            is_tree,
+            keys,
+            deadline: Instant::now() + grace_period.max(KILL_SETTLE_PERIOD),
+            escalation,
+            awaiting_confirmation: false,
+        };
+        (termination, report)
+    }
+
+    pub fn target(&self) -> String {
+        if self.is_tree {
+            format!("tree {}", self.root_pid)
+        } else {
+            self.root_pid.to_string()
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    pub fn awaiting_confirmation(&self) -> bool {
+        self.awaiting_confirmation
+    }
+
+    pub fn watched_pids(&self) -> impl Iterator<Item = sysinfo::Pid> {
+        self.keys
+            .iter()
+            .map(|key| sysinfo::Pid::from_u32(key.pid()))
+    }
+
+    pub fn kill_survivors(&mut self, system: &sysinfo::System) -> String {
+        let report =
+            signals::send_signal_to_all(system, &self.survivors(system), sysinfo::Signal::Kill);
+        self.escalation = Escalation::Never;
+        self.awaiting_confirmation = false;
+        self.deadline = Instant::now() + KILL_SETTLE_PERIOD;
+        report.to_string()
+    }
+
+    fn survivors(&self, system: &sysinfo::System) -> Vec<u32> {
+        self.keys
+            .iter()
+            .filter(|key| key.is_running(system))
+            .filter(|key| {
+                system
+                    .process(sysinfo::Pid::from_u32(key.pid()))
+                    .is_some_and(|process| process.status() != sysinfo::ProcessStatus::Zombie)
+            })
+            .map(data::ProcessKey::pid)
+            .collect()
+    }
+
+    fn exited_message(&self) -> String {
+        if self.is_tree {
+            format!(
+                "All {} processes of {} exited",
+                self.keys.len(),
+                self.target()
+            )
+        } else {
+            format!("{} exited", self.target())
+        }
+    }
+
+    fn survivors_message(&self, survivors: &[u32]) -> String {
+        let survivor_list: Vec<String> = survivors.iter().map(u32::to_string).collect();
+        format!(
+            "{} of {} processes of {} survived: {}",
+            survivors.len(),
+            self.keys.len(),
+            self.target(),
+            survivor_list.join(", ")
+        )
+    }
+}
+
+pub fn check_terminations(
+    system: &sysinfo::System,
+    terminations: &mut Vec<Termination>,
+) -> Option<String> {
+    let now = Instant::now();
+    let mut message = None;
+
+    terminations.retain_mut(|termination| {
+        let survivors = termination.survivors(system);
+        if survivors.is_empty() {
+            message = Some(termination.exited_message());
+            return false;
+        }
+
+        if now < termination.deadline || termination.awaiting_confirmation {
+            return true;
+        }
+
+        match termination.escalation {
+            Escalation::Automatic => {
+                message = Some(format!(
+                    "Grace period expired; {}",
+                    termination.kill_survivors(system)
+                ));
+                true
+            }
+            Escalation::Confirm => {
+                termination.awaiting_confirmation = true;
+                message = Some(format!(
+                    "{} is still running after the grace period",
+                    termination.target()
+                ));
+                true
+            }
+            Escalation::Never => {
+                message = Some(termination.survivors_message(&survivors));
+                false
+            }
+        }
+    });
+
+    message
+}
@@ -1,4 +1,5 @@
 use crate::processes::data;
+use crate::processes::data::procfs;
 
 const UNKNOWN_PROCESS_PATH: &str = "-";
 const UNKNOWN_PROCESS_NAME: &str = "-";
@@ -6,26 +7,35 @@ const UNKNOWN_USER: &str = "-";
 
 pub fn extract_processes_info(
     processes: &[&sysinfo::Process],
-    indentations: Vec<usize>,
     users: &sysinfo::Users,
     cpu_count: usize,
+    proc_cache: &procfs::ProcCache,
 ) -> Vec<data::ProcessInfo> {
     processes
         .iter()
-        .zip(indentations)
-        .map(|(process, child_depth)| extract_info(process, child_depth, users, cpu_count))
+        .map(|process| extract_info(process, users, cpu_count, proc_cache))
         .collect()
 }
 
 fn extract_info(
     process: &sysinfo::Process,
-    child_depth: usize,
     users: &sysinfo::Users,
     cpu_count: usize,
+    proc_cache: &procfs::ProcCache,
 ) -> data::ProcessInfo {
+    let stat = proc_cache.stat(process);
     data::ProcessInfo {
-        child_depth,
+        child_depth: 0,
+        tree_guides: Vec::new(),
+        has_children: false,
+        collapsed: false,
+        filter_context: false,
         id: extract_id(process),
+        parent_id: extract_parent_id(process),
+        session_id: extract_session_id(process),
+        process_group: stat.map(|stat| stat.process_group),
+        tty: stat.and_then(|stat| procfs::tty_name(stat.tty_nr)),
+        threads: extract_threads(process, stat),
         name: extract_name(process)
             .unwrap_or(UNKNOWN_PROCESS_NAME)
             .to_owned(),
@@ -33,13 +43,20 @@ fn extract_info(
             .unwrap_or(UNKNOWN_USER)
             .to_owned(),
         memory: extract_memory(process),
+        virtual_memory: extract_virtual_memory(process),
+        memory_breakdown: proc_cache.memory_breakdown(process),
         cpu: extract_cpu(process, cpu_count),
+        nice: stat.map(|stat| stat.nice),
         disk_read: extract_disk_read(process),
         disk_write: extract_disk_write(process),
         path: extract_path(process)
             .unwrap_or(UNKNOWN_PROCESS_PATH)
             .to_owned(),
+        command: extract_command(process),
         status: extract_status(process),
+        start_time: extract_start_time(process),
+        run_time: extract_run_time(process),
+        subtree_usage: None,
     }
 }
 
@@ -47,11 +64,15 @@ fn extract_id(process: &sysinfo::Process) -> u32 {
     process.pid().as_u32()
 }
 
+fn extract_parent_id(process: &sysinfo::Process) -> Option<u32> {
+    process.parent().map(sysinfo::Pid::as_u32)
+}
+
 pub fn extract_name(process: &sysinfo::Process) -> Option<&str> {
     process.name().to_str()
 }
 
-fn extract_user<'a>(process: &sysinfo::Process, users: &'a sysinfo::Users) -> Option<&'a str> {
+pub fn extract_user<'a>(process: &sysinfo::Process, users: &'a sysinfo::Users) -> Option<&'a str> {
     process
         .user_id()
         .and_then(|uid| users.get_user_by_id(uid))
@@ -62,8 +83,21 @@ fn extract_memory(process: &sysinfo::Process) -> u64 {
     process.memory()
 }
 
-fn extract_cpu(process: &sysinfo::Process, cpu_count: usize) -> String {
-    format!("{:.2}%", process.cpu_usage() / cpu_count as f32)
+fn extract_virtual_memory(process: &sysinfo::Process) -> u64 {
+    process.virtual_memory()
+}
+
+fn extract_cpu(process: &sysinfo::Process, cpu_count: usize) -> f32 {
+    process.cpu_usage() / cpu_count.max(1) as f32
+}
+
+fn extract_session_id(process: &sysinfo::Process) -> Option<u32> {
+    process.session_id().map(sysinfo::Pid::as_u32)
+}
+
+fn extract_threads(process: &sysinfo::Process, stat: Option<&procfs::ProcStat>) -> Option<usize> {
+    stat.map(|stat| stat.threads)
+        .or_else(|| process.tasks().map(std::collections::HashSet::len))
 }
 
 fn extract_disk_read(process: &sysinfo::Process) -> u64 {
@@ -79,6 +113,36 @@ pub fn extract_path(process: &sysinfo::Process) -> Option<&str> {
     path.to_str()
 }
 
-fn extract_status(process: &sysinfo::Process) -> String {
-    process.status().to_string()
+pub fn extract_command(process: &sysinfo::Process) -> String {
+    process
+        .cmd()
+        .iter()
+        .map(|argument| quote_argument(&argument.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn quote_argument(argument: &str) -> String {
+    let needs_quoting = argument.is_empty()
+        || !argument.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '_' | '@' | '%' | '+' | '=' | ':' | ',' | '.' | '/' | '-')
+        });
+    if needs_quoting {
+        format!("'{}'", argument.replace('\'', "'\\''"))
+    } else {
+        argument.to_owned()
+    }
+}
+
+pub fn extract_status(process: &sysinfo::Process) -> sysinfo::ProcessStatus {
+    process.status()
+}
+
+fn extract_start_time(process: &sysinfo::Process) -> u64 {
+    process.start_time()
+}
+
+fn extract_run_time(process: &sysinfo::Process) -> u64 {
+    process.run_time()
 }
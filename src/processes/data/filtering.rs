@@ -0,0 +1,923 @@
+use crate::processes::data;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+pub enum FilterQuery {
+    All,
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+    Not(Box<Self>),
+    Term(FilterTerm),
+}
+
+pub enum FilterTerm {
+    Text(TextField, TextMatch),
+    Number(NumberField, Comparison, f64),
+    Status(String),
+}
+
+#[derive(Clone, Copy)]
+pub enum TextField {
+    Any,
+    Name,
+    User,
+    Path,
+    Command,
+    Tty,
+}
+
+pub enum TextMatch {
+    Contains(String),
+    ContainsIgnoreCase(String),
+    Equals(String),
+    EqualsIgnoreCase(String),
+    Regex(regex::Regex),
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct FilterOptions {
+    pub case_insensitive: bool,
+    pub whole_word: bool,
+    pub regex: bool,
+}
+
+#[derive(Clone, Copy)]
+pub enum NumberField {
+    Id,
+    Memory,
+    Cpu,
+    Nice,
+    DiskRead,
+    DiskWrite,
+    StartTime,
+    RunTime,
+    ParentId,
+    Threads,
+    Session,
+    ProcessGroup,
+    VirtualMemory,
+    SharedMemory,
+    PrivateMemory,
+    ProportionalMemory,
+    Swap,
+}
+
+#[derive(Clone, Copy)]
+pub enum Comparison {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+pub const STATUS_FILTER_OPTIONS: [sysinfo::ProcessStatus; 11] = [
+    sysinfo::ProcessStatus::Run,
+    sysinfo::ProcessStatus::Sleep,
+    sysinfo::ProcessStatus::Idle,
+    sysinfo::ProcessStatus::UninterruptibleDiskSleep,
+    sysinfo::ProcessStatus::Waking,
+    sysinfo::ProcessStatus::Wakekill,
+    sysinfo::ProcessStatus::Parked,
+    sysinfo::ProcessStatus::Stop,
+    sysinfo::ProcessStatus::Tracing,
+    sysinfo::ProcessStatus::Zombie,
+    sysinfo::ProcessStatus::Dead,
+];
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ColumnFilters {
+    pub name: String,
+    pub id: String,
+    pub user: String,
+    pub users: BTreeSet<String>,
+    pub memory: String,
+    pub cpu: String,
+    pub nice: String,
+    pub disk_read: String,
+    pub disk_write: String,
+    pub path: String,
+    pub start_time: String,
+    pub run_time: String,
+    pub command: String,
+    pub parent_id: String,
+    pub threads: String,
+    pub session: String,
+    pub process_group: String,
+    pub tty: String,
+    pub virtual_memory: String,
+    pub shared_memory: String,
+    pub private_memory: String,
+    pub proportional_memory: String,
+    pub swap: String,
+    pub statuses: BTreeSet<String>,
+}
+
+pub struct FilterError {
+    message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl FilterError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl FilterQuery {
+    pub fn parse(input: &str, options: FilterOptions) -> Result<Self, FilterError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens: tokens.into_iter().peekable(),
+            options,
+        };
+
+        if parser.tokens.peek().is_none() {
+            return Ok(Self::All);
+        }
+
+        let query = parser.parse_or()?;
+        match parser.tokens.next() {
+            None => Ok(query),
+            Some(Token::CloseParen) => Err(FilterError::new("unmatched ')'")),
+            Some(token) => Err(FilterError::new(format!("unexpected {token}"))),
+        }
+    }
+
+    pub fn and(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::All, query) | (query, Self::All) => query,
+            (a, b) => Self::And(Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+
+    fn any_of(queries: impl IntoIterator<Item = Self>) -> Self {
+        queries.into_iter().reduce(Self::or).unwrap_or(Self::All)
+    }
+
+    pub fn matches(&self, process_info: &data::ProcessInfo) -> bool {
+        match self {
+            Self::All => true,
+            Self::And(a, b) => a.matches(process_info) && b.matches(process_info),
+            Self::Or(a, b) => a.matches(process_info) || b.matches(process_info),
+            Self::Not(query) => !query.matches(process_info),
+            Self::Term(term) => term.matches(process_info),
+        }
+    }
+}
+
+impl ColumnFilters {
+    pub fn text_mut(&mut self, column: data::SortCategory) -> Option<&mut String> {
+        match column {
+            data::SortCategory::Name => Some(&mut self.name),
+            data::SortCategory::Id => Some(&mut self.id),
+            data::SortCategory::User => Some(&mut self.user),
+            data::SortCategory::Memory => Some(&mut self.memory),
+            data::SortCategory::Cpu => Some(&mut self.cpu),
+            data::SortCategory::Nice => Some(&mut self.nice),
+            data::SortCategory::DiskRead => Some(&mut self.disk_read),
+            data::SortCategory::DiskWrite => Some(&mut self.disk_write),
+            data::SortCategory::Path => Some(&mut self.path),
+            data::SortCategory::StartTime => Some(&mut self.start_time),
+            data::SortCategory::RunTime => Some(&mut self.run_time),
+            data::SortCategory::Command => Some(&mut self.command),
+            data::SortCategory::ParentId => Some(&mut self.parent_id),
+            data::SortCategory::Threads => Some(&mut self.threads),
+            data::SortCategory::Session => Some(&mut self.session),
+            data::SortCategory::ProcessGroup => Some(&mut self.process_group),
+            data::SortCategory::Tty => Some(&mut self.tty),
+            data::SortCategory::VirtualMemory => Some(&mut self.virtual_memory),
+            data::SortCategory::SharedMemory => Some(&mut self.shared_memory),
+            data::SortCategory::PrivateMemory => Some(&mut self.private_memory),
+            data::SortCategory::ProportionalMemory => Some(&mut self.proportional_memory),
+            data::SortCategory::Swap => Some(&mut self.swap),
+            data::SortCategory::Status => None,
+        }
+    }
+
+    pub fn query(&self, options: FilterOptions) -> FilterQuery {
+        let users = FilterQuery::any_of(self.users.iter().map(|user| {
+            FilterQuery::Term(FilterTerm::Text(
+                TextField::User,
+                TextMatch::Equals(user.clone()),
+            ))
+        }));
+        let statuses = FilterQuery::any_of(
+            self.statuses
+                .iter()
+                .map(|status| FilterQuery::Term(FilterTerm::Status(status.to_lowercase()))),
+        );
+
+        data::SortCategory::ALL
+            .into_iter()
+            .filter_map(|column| self.column_query(column, options).ok())
+            .fold(users.and(statuses), FilterQuery::and)
+    }
+
+    pub fn column_error(
+        &self,
+        column: data::SortCategory,
+        options: FilterOptions,
+    ) -> Option<FilterError> {
+        self.column_query(column, options).err()
+    }
+
+    fn column_query(
+        &self,
+        column: data::SortCategory,
+        options: FilterOptions,
+    ) -> Result<FilterQuery, FilterError> {
+        match column {
+            data::SortCategory::Name => parse_column_text(TextField::Name, &self.name, options),
+            data::SortCategory::Id => parse_column_ids(NumberField::Id, &self.id),
+            data::SortCategory::User => parse_column_text(TextField::User, &self.user, options),
+            data::SortCategory::Memory => parse_column_range(NumberField::Memory, &self.memory),
+            data::SortCategory::Cpu => parse_column_range(NumberField::Cpu, &self.cpu),
+            data::SortCategory::Nice => parse_column_range(NumberField::Nice, &self.nice),
+            data::SortCategory::DiskRead => {
+                parse_column_range(NumberField::DiskRead, &self.disk_read)
+            }
+            data::SortCategory::DiskWrite => {
+                parse_column_range(NumberField::DiskWrite, &self.disk_write)
+            }
+            data::SortCategory::Path => parse_column_text(TextField::Path, &self.path, options),
+            data::SortCategory::StartTime => {
+                parse_column_range(NumberField::StartTime, &self.start_time)
+            }
+            data::SortCategory::RunTime => parse_column_range(NumberField::RunTime, &self.run_time),
+            data::SortCategory::Command => {
+                parse_column_text(TextField::Command, &self.command, options)
+            }
+            data::SortCategory::ParentId => {
+                parse_column_ids(NumberField::ParentId, &self.parent_id)
+            }
+            data::SortCategory::Threads => parse_column_range(NumberField::Threads, &self.threads),
+            data::SortCategory::Session => parse_column_ids(NumberField::Session, &self.session),
+            data::SortCategory::ProcessGroup => {
+                parse_column_ids(NumberField::ProcessGroup, &self.process_group)
+            }
+            data::SortCategory::Tty => parse_column_text(TextField::Tty, &self.tty, options),
+            data::SortCategory::VirtualMemory => {
+                parse_column_range(NumberField::VirtualMemory, &self.virtual_memory)
+            }
+            data::SortCategory::SharedMemory => {
+                parse_column_range(NumberField::SharedMemory, &self.shared_memory)
+            }
+            data::SortCategory::PrivateMemory => {
+                parse_column_range(NumberField::PrivateMemory, &self.private_memory)
+            }
+            data::SortCategory::ProportionalMemory => {
+                parse_column_range(NumberField::ProportionalMemory, &self.proportional_memory)
+            }
+            data::SortCategory::Swap => parse_column_range(NumberField::Swap, &self.swap),
+            data::SortCategory::Status => Ok(FilterQuery::All),
+        }
+    }
+}
+
+fn parse_column_text(
+    field: TextField,
+    text: &str,
+    options: FilterOptions,
+) -> Result<FilterQuery, FilterError> {
+    if text.is_empty() {
+        return Ok(FilterQuery::All);
+    }
+    let text_match = contains_match(text, options)?;
+    Ok(FilterQuery::Term(FilterTerm::Text(field, text_match)))
+}
+
+fn parse_column_range(field: NumberField, text: &str) -> Result<FilterQuery, FilterError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(FilterQuery::All);
+    }
+
+    if let Some((start, end)) = text.split_once("..") {
+        let start = match start.trim() {
+            "" => FilterQuery::All,
+            start => FilterQuery::Term(parse_number_term(field, ">=", start)?),
+        };
+        let end = match end.trim() {
+            "" => FilterQuery::All,
+            end => FilterQuery::Term(parse_number_term(field, "<=", end)?),
+        };
+        return Ok(start.and(end));
+    }
+
+    let operator_length = text
+        .find(|c: char| !matches!(c, '<' | '>' | '='))
+        .unwrap_or(text.len());
+    let (operator, value) = text.split_at(operator_length);
+    let operator = if operator.is_empty() { ">=" } else { operator };
+    Ok(FilterQuery::Term(parse_number_term(
+        field,
+        operator,
+        value.trim(),
+    )?))
+}
+
+fn parse_column_ids(field: NumberField, text: &str) -> Result<FilterQuery, FilterError> {
+    let ranges = text
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            if item.contains("..") || item.contains('-') {
+                parse_column_range(field, &item.replacen('-', "..", 1))
+            } else {
+                parse_number_term(field, "=", item).map(FilterQuery::Term)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(FilterQuery::any_of(ranges))
+}
+
+impl FilterTerm {
+    fn matches(&self, process_info: &data::ProcessInfo) -> bool {
+        match self {
+            Self::Text(TextField::Any, text_match) => {
+                text_match.matches(&process_info.name)
+                    || text_match.matches(&process_info.user)
+                    || text_match.matches(&process_info.path)
+                    || text_match.matches(&process_info.command)
+            }
+            Self::Text(TextField::Name, text_match) => text_match.matches(&process_info.name),
+            Self::Text(TextField::User, text_match) => text_match.matches(&process_info.user),
+            Self::Text(TextField::Path, text_match) => text_match.matches(&process_info.path),
+            Self::Text(TextField::Command, text_match) => text_match.matches(&process_info.command),
+            Self::Text(TextField::Tty, text_match) => {
+                text_match.matches(process_info.tty.as_deref().unwrap_or_default())
+            }
+            Self::Number(field, comparison, value) => {
+                comparison.matches(field.value(process_info), *value)
+            }
+            Self::Status(status) => {
+                let display_name = process_info.status.to_string().to_lowercase();
+                let variant_name = format!("{:?}", process_info.status).to_lowercase();
+                display_name.starts_with(status.as_str()) || status.starts_with(&variant_name)
+            }
+        }
+    }
+}
+
+impl TextMatch {
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Contains(pattern) => text.contains(pattern.as_str()),
+            Self::ContainsIgnoreCase(pattern) => text.to_lowercase().contains(pattern.as_str()),
+            Self::Equals(pattern) => text == pattern,
+            Self::EqualsIgnoreCase(pattern) => text.to_lowercase() == *pattern,
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+impl NumberField {
+    fn value(self, process_info: &data::ProcessInfo) -> f64 {
+        match self {
+            Self::Id => f64::from(process_info.id),
+            Self::Memory => process_info.memory as f64,
+            Self::Cpu => f64::from(process_info.cpu),
+            Self::Nice => process_info.nice.map_or(f64::NAN, f64::from),
+            Self::DiskRead => process_info.disk_read as f64,
+            Self::DiskWrite => process_info.disk_write as f64,
+            Self::StartTime => process_info.start_time as f64,
+            Self::RunTime => process_info.run_time as f64,
+            Self::ParentId => process_info.parent_id.map_or(f64::NAN, f64::from),
+            Self::Threads => process_info
+                .threads
+                .map_or(f64::NAN, |threads| threads as f64),
+            Self::Session => process_info.session_id.map_or(f64::NAN, f64::from),
+            Self::ProcessGroup => process_info.process_group.map_or(f64::NAN, f64::from),
+            Self::VirtualMemory => process_info.virtual_memory as f64,
+            Self::SharedMemory => breakdown_value(process_info, |breakdown| breakdown.shared),
+            Self::PrivateMemory => breakdown_value(process_info, |breakdown| breakdown.private),
+            Self::ProportionalMemory => {
+                breakdown_value(process_info, |breakdown| breakdown.proportional)
+            }
+            Self::Swap => breakdown_value(process_info, |breakdown| breakdown.swap),
+        }
+    }
+
+    fn parse_value(self, value: &str) -> Result<f64, FilterError> {
+        match self {
+            Self::Id
+            | Self::Cpu
+            | Self::Nice
+            | Self::ParentId
+            | Self::Threads
+            | Self::Session
+            | Self::ProcessGroup => value
+                .trim_end_matches('%')
+                .parse::<f64>()
+                .map_err(|error| FilterError::new(format!("invalid number {value:?}: {error}"))),
+            Self::Memory
+            | Self::DiskRead
+            | Self::DiskWrite
+            | Self::VirtualMemory
+            | Self::SharedMemory
+            | Self::PrivateMemory
+            | Self::ProportionalMemory
+            | Self::Swap => value
+                .parse::<bytesize::ByteSize>()
+                .map(|size| size.as_u64() as f64)
+                .map_err(|error| FilterError::new(format!("invalid size {value:?}: {error}"))),
+            Self::StartTime => parse_start_time(value).ok_or_else(|| {
+                FilterError::new(format!(
+                    "invalid time {value:?}, expected HH:MM, YYYY-MM-DD or YYYY-MM-DDTHH:MM"
+                ))
+            }),
+            Self::RunTime => parse_duration(value).ok_or_else(|| {
+                FilterError::new(format!(
+                    "invalid duration {value:?}, expected e.g. 90s, 5m, 1h30m, 2d"
+                ))
+            }),
+        }
+    }
+}
+
+fn breakdown_value(
+    process_info: &data::ProcessInfo,
+    value: impl Fn(data::MemoryBreakdown) -> u64,
+) -> f64 {
+    process_info
+        .memory_breakdown
+        .map_or(f64::NAN, |breakdown| value(breakdown) as f64)
+}
+
+impl Comparison {
+    fn matches(self, actual: f64, expected: f64) -> bool {
+        match self {
+            Self::Equal => actual == expected,
+            Self::Greater => actual > expected,
+            Self::GreaterOrEqual => actual >= expected,
+            Self::Less => actual < expected,
+            Self::LessOrEqual => actual <= expected,
+        }
+    }
+}
+
+enum Token {
+    Word(String),
+    Quoted(String),
+    OpenParen,
+    CloseParen,
+    Not,
+    And,
+    Or,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "{word:?}"),
+            Self::Quoted(text) => write!(f, "\"{text}\""),
+            Self::OpenParen => f.write_str("'('"),
+            Self::CloseParen => f.write_str("')'"),
+            Self::Not => f.write_str("NOT"),
+            Self::And => f.write_str("AND"),
+            Self::Or => f.write_str("OR"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::OpenParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::CloseParen);
+            }
+            '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            '"' => {
+                chars.next();
+                tokens.push(Token::Quoted(take_quoted(&mut chars)?));
+            }
+            _ => {
+                let word = take_word(&mut chars)?;
+                tokens.push(match word.as_str() {
+                    "AND" | "&&" => Token::And,
+                    "OR" | "||" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn take_quoted(chars: &mut Peekable<Chars<'_>>) -> Result<String, FilterError> {
+    let mut text = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(text),
+            Some('\\') => {
+                if let Some(escaped) = chars.next() {
+                    text.push(escaped);
+                }
+            }
+            Some(c) => text.push(c),
+            None => return Err(FilterError::new("unterminated quote")),
+        }
+    }
+}
+
+fn take_word(chars: &mut Peekable<Chars<'_>>) -> Result<String, FilterError> {
+    let mut word = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            break;
+        }
+        chars.next();
+        if c == '"' {
+            word.push_str(&take_quoted(chars)?);
+        } else {
+            word.push(c);
+        }
+    }
+    Ok(word)
+}
+
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+    options: FilterOptions,
+}
+
+impl Parser {
+    fn parse_or(&mut self) -> Result<FilterQuery, FilterError> {
+        let mut query = self.parse_and()?;
+        while self
+            .tokens
+            .next_if(|token| matches!(token, Token::Or))
+            .is_some()
+        {
+            let right = self.parse_and()?;
+            query = FilterQuery::Or(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterQuery, FilterError> {
+        let mut query = self.parse_unary()?;
+        loop {
+            match self.tokens.peek() {
+                Some(Token::And) => {
+                    self.tokens.next();
+                }
+                Some(Token::Word(_) | Token::Quoted(_) | Token::OpenParen | Token::Not) => {}
+                Some(Token::Or | Token::CloseParen) | None => return Ok(query),
+            }
+            let right = self.parse_unary()?;
+            query = FilterQuery::And(Box::new(query), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterQuery, FilterError> {
+        if self
+            .tokens
+            .next_if(|token| matches!(token, Token::Not))
+            .is_some()
+        {
+            return Ok(FilterQuery::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterQuery, FilterError> {
+        match self.tokens.next() {
+            Some(Token::OpenParen) => {
+                let query = self.parse_or()?;
+                match self.tokens.next() {
+                    Some(Token::CloseParen) => Ok(query),
+                    _ => Err(FilterError::new("missing ')'")),
+                }
+            }
+            Some(Token::Quoted(text)) => {
+                let text_match = contains_match(&text, self.options)?;
+                Ok(FilterQuery::Term(FilterTerm::Text(
+                    TextField::Any,
+                    text_match,
+                )))
+            }
+            Some(Token::Word(word)) => parse_term(&word, self.options).map(FilterQuery::Term),
+            Some(token) => Err(FilterError::new(format!(
+                "expected a filter term, found {token}"
+            ))),
+            None => Err(FilterError::new("expected a filter term")),
+        }
+    }
+}
+
+enum Field {
+    Text(TextField),
+    Number(NumberField),
+    Status,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        let field = match name.to_lowercase().as_str() {
+            "name" => Self::Text(TextField::Name),
+            "user" => Self::Text(TextField::User),
+            "path" => Self::Text(TextField::Path),
+            "cmd" | "command" => Self::Text(TextField::Command),
+            "tty" => Self::Text(TextField::Tty),
+            "status" => Self::Status,
+            "pid" | "id" => Self::Number(NumberField::Id),
+            "ppid" | "parent" => Self::Number(NumberField::ParentId),
+            "threads" => Self::Number(NumberField::Threads),
+            "sid" | "session" => Self::Number(NumberField::Session),
+            "pgid" | "pgrp" => Self::Number(NumberField::ProcessGroup),
+            "mem" | "memory" | "rss" => Self::Number(NumberField::Memory),
+            "virt" => Self::Number(NumberField::VirtualMemory),
+            "shr" | "shared" => Self::Number(NumberField::SharedMemory),
+            "uss" => Self::Number(NumberField::PrivateMemory),
+            "pss" => Self::Number(NumberField::ProportionalMemory),
+            "swap" => Self::Number(NumberField::Swap),
+            "cpu" => Self::Number(NumberField::Cpu),
+            "nice" => Self::Number(NumberField::Nice),
+            "read" => Self::Number(NumberField::DiskRead),
+            "write" => Self::Number(NumberField::DiskWrite),
+            "start" | "started" => Self::Number(NumberField::StartTime),
+            "age" | "runtime" | "run_time" => Self::Number(NumberField::RunTime),
+            _ => return None,
+        };
+        Some(field)
+    }
+}
+
+fn parse_term(word: &str, options: FilterOptions) -> Result<FilterTerm, FilterError> {
+    let field_term = word
+        .find([':', '~', '=', '>', '<'])
+        .map(|operator_start| word.split_at(operator_start))
+        .and_then(|(name, rest)| Some((Field::from_name(name)?, name, rest)));
+    let Some((field, name, rest)) = field_term else {
+        return Ok(FilterTerm::Text(
+            TextField::Any,
+            contains_match(word, options)?,
+        ));
+    };
+
+    let operator_length = if rest.starts_with(">=") || rest.starts_with("<=") {
+        2
+    } else {
+        1
+    };
+    let (operator, value) = rest.split_at(operator_length);
+
+    if value.is_empty() {
+        return Err(FilterError::new(format!(
+            "missing value after {name}{operator}"
+        )));
+    }
+
+    match field {
+        Field::Text(field) => parse_text_term(field, operator, value, options),
+        Field::Number(field) => parse_number_term(field, operator, value),
+        Field::Status => match operator {
+            ":" | "=" => Ok(FilterTerm::Status(value.to_lowercase())),
+            _ => Err(FilterError::new(format!(
+                "status does not support {operator:?}"
+            ))),
+        },
+    }
+}
+
+fn parse_text_term(
+    field: TextField,
+    operator: &str,
+    value: &str,
+    options: FilterOptions,
+) -> Result<FilterTerm, FilterError> {
+    let text_match = match operator {
+        ":" => contains_match(value, options)?,
+        "=" if options.case_insensitive => TextMatch::EqualsIgnoreCase(value.to_lowercase()),
+        "=" => TextMatch::Equals(value.to_owned()),
+        "~" => regex_match(value, options.case_insensitive)?,
+        _ => {
+            return Err(FilterError::new(format!(
+                "text fields do not support {operator:?}"
+            )));
+        }
+    };
+    Ok(FilterTerm::Text(field, text_match))
+}
+
+fn contains_match(value: &str, options: FilterOptions) -> Result<TextMatch, FilterError> {
+    if options.regex || options.whole_word {
+        let pattern = if options.regex {
+            value.to_owned()
+        } else {
+            regex::escape(value)
+        };
+        let pattern = if options.whole_word {
+            format!(r"\b(?:{pattern})\b")
+        } else {
+            pattern
+        };
+        return regex_match(&pattern, options.case_insensitive);
+    }
+
+    if options.case_insensitive {
+        Ok(TextMatch::ContainsIgnoreCase(value.to_lowercase()))
+    } else {
+        Ok(TextMatch::Contains(value.to_owned()))
+    }
+}
+
+fn regex_match(pattern: &str, case_insensitive: bool) -> Result<TextMatch, FilterError> {
+    regex::RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map(TextMatch::Regex)
+        .map_err(|error| FilterError::new(format!("invalid regex {pattern:?}: {error}")))
+}
+
+fn parse_number_term(
+    field: NumberField,
+    operator: &str,
+    value: &str,
+) -> Result<FilterTerm, FilterError> {
+    let comparison = match operator {
+        ":" | "=" => Comparison::Equal,
+        ">" => Comparison::Greater,
+        ">=" => Comparison::GreaterOrEqual,
+        "<" => Comparison::Less,
+        "<=" => Comparison::LessOrEqual,
+        _ => {
+            return Err(FilterError::new(format!(
+                "numeric fields do not support {operator:?}"
+            )));
+        }
+    };
+    Ok(FilterTerm::Number(
+        field,
+        comparison,
+        field.parse_value(value)?,
+    ))
+}
+
+fn parse_start_time(value: &str) -> Option<f64> {
+    const DATE_TIME_FORMATS: [&str; 4] = [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ];
+    const TIME_FORMATS: [&str; 2] = ["%H:%M:%S", "%H:%M"];
+
+    let date_time = DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(chrono::NaiveTime::MIN))
+        })
+        .or_else(|| {
+            TIME_FORMATS
+                .iter()
+                .find_map(|format| chrono::NaiveTime::parse_from_str(value, format).ok())
+                .map(|time| chrono::Local::now().date_naive().and_time(time))
+        })?;
+    date_time
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|time| time.timestamp() as f64)
+}
+
+fn parse_duration(value: &str) -> Option<f64> {
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Some(seconds);
+    }
+
+    let mut seconds = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_length = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, after_number) = rest.split_at(number_length);
+        let unit_length = after_number
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after_number.len());
+        let (unit, after_unit) = after_number.split_at(unit_length);
+        let unit_seconds = match unit.to_lowercase().as_str() {
+            "s" | "sec" | "secs" => 1.0,
+            "m" | "min" | "mins" => 60.0,
+            "h" | "hr" | "hrs" => 3_600.0,
+            "d" | "day" | "days" => 86_400.0,
+            "w" | "week" | "weeks" => 604_800.0,
+            _ => return None,
+        };
+        seconds += number.parse::<f64>().ok()? * unit_seconds;
+        rest = after_unit;
+    }
+    Some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FilterOptions, FilterQuery};
+    use crate::processes::data;
+
+    fn process_info(name: &str) -> data::ProcessInfo {
+        let mut process_info = data::ProcessInfo::for_test(1);
+        process_info.name = name.to_owned();
+        process_info.user = "root".to_owned();
+        process_info.memory = 600_000_000;
+        process_info.cpu = 10.0;
+        process_info.path = "/usr/bin/worker".to_owned();
+        process_info.status = sysinfo::ProcessStatus::Sleep;
+        process_info
+    }
+
+    fn matches(query: &str, name: &str) -> bool {
+        let Ok(query) = FilterQuery::parse(query, FilterOptions::default()) else {
+            panic!("failed to parse {query:?}");
+        };
+        query.matches(&process_info(name))
+    }
+
+    fn parse_error(query: &str) -> String {
+        match FilterQuery::parse(query, FilterOptions::default()) {
+            Ok(_) => panic!("expected {query:?} to be rejected"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn example_query() {
+        let query =
+            "user:root cpu>5 mem>500MB status:sleeping name~^kworker path:/usr/bin !name:bash";
+        assert!(matches(query, "kworker/0:1"));
+        assert!(!matches(query, "kworker-bash"));
+        assert!(!matches(query, "worker"));
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let query = "name:a name:b OR name:c";
+        assert!(matches(query, "ab"));
+        assert!(matches(query, "c"));
+        assert!(!matches(query, "a"));
+        assert!(matches("name:c OR name:a name:b", "ab"));
+    }
+
+    #[test]
+    fn parentheses_group_terms() {
+        let query = "name:a (name:b OR name:c)";
+        assert!(matches(query, "ac"));
+        assert!(!matches(query, "c"));
+        assert!(matches("!(name:a OR name:b) name:c", "c"));
+        assert!(!matches("!(name:a OR name:b) name:c", "ac"));
+    }
+
+    #[test]
+    fn unknown_field_prefix_is_free_text() {
+        assert!(matches("kworker/0:1", "kworker/0:1"));
+        assert!(!matches("kworker/0:1", "kworker/0:2"));
+        assert!(matches("a=b", "x a=b y"));
+        assert!(!matches("size>5", "worker"));
+        assert!(matches("name:kworker/0:1", "kworker/0:1"));
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        assert!(parse_error("(name:a").contains(')'));
+        assert!(parse_error("name:a)").contains("unmatched ')'"));
+        assert!(parse_error("name:").contains("missing value"));
+        assert!(parse_error("name:\"abc").contains("quote"));
+    }
+}
@@ -1,33 +1,158 @@
 mod extraction;
+mod filtering;
+mod procfs;
 mod sorting;
 mod tree;
 
-use crate::app;
+use crate::{app, processes};
 use extraction::extract_processes_info;
+use std::collections::BTreeSet;
+use std::ops::AddAssign;
 
+pub use extraction::extract_command;
 pub use extraction::extract_name;
 pub use extraction::extract_path;
+pub use extraction::extract_status;
+pub use extraction::extract_user;
+pub use filtering::ColumnFilters;
+pub use filtering::FilterOptions;
+pub use filtering::FilterQuery;
+pub use filtering::STATUS_FILTER_OPTIONS;
+pub use procfs::MemoryBreakdown;
+pub use procfs::ProcCache;
+pub use procfs::ProcessLimit;
+pub use procfs::read_limits;
+pub use procfs::read_memory_breakdown;
+pub use procfs::read_memory_maps;
+pub use procfs::read_proc_stat;
+pub use procfs::tty_name;
 pub use sorting::SortCategory;
 pub use sorting::SortDirection;
+pub use sorting::SortKey;
 pub use sorting::SortMethod;
 pub use tree::ProcessTree;
 
 pub struct ProcessInfo {
     pub child_depth: usize,
+    pub tree_guides: Vec<bool>,
+    pub has_children: bool,
+    pub collapsed: bool,
+    pub filter_context: bool,
     pub id: u32,
+    pub parent_id: Option<u32>,
+    pub session_id: Option<u32>,
+    pub process_group: Option<u32>,
+    pub tty: Option<String>,
+    pub threads: Option<usize>,
     pub name: String,
     pub user: String,
     pub memory: u64,
-    pub cpu: String,
+    pub virtual_memory: u64,
+    pub memory_breakdown: Option<MemoryBreakdown>,
+    pub cpu: f32,
+    pub nice: Option<i32>,
     pub disk_read: u64,
     pub disk_write: u64,
     pub path: String,
-    pub status: String,
+    pub command: String,
+    pub status: sysinfo::ProcessStatus,
+    pub start_time: u64,
+    pub run_time: u64,
+    pub subtree_usage: Option<ResourceUsage>,
+}
+
+impl ProcessInfo {
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey {
+            pid: self.id,
+            start_time: self.start_time,
+        }
+    }
+
+    pub fn resource_usage(&self) -> ResourceUsage {
+        ResourceUsage {
+            memory: self.memory,
+            proportional_memory: self
+                .memory_breakdown
+                .map_or(0, |breakdown| breakdown.proportional),
+            unknown_proportional_memory: usize::from(self.memory_breakdown.is_none()),
+            cpu: self.cpu,
+            disk_read: self.disk_read,
+            disk_write: self.disk_write,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pid: u32,
+    start_time: u64,
+}
+
+impl ProcessKey {
+    pub fn from_process(process: &sysinfo::Process) -> Self {
+        Self {
+            pid: process.pid().as_u32(),
+            start_time: process.start_time(),
+        }
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn is_running(&self, system: &sysinfo::System) -> bool {
+        system
+            .process(sysinfo::Pid::from_u32(self.pid))
+            .is_some_and(|process| process.start_time() == self.start_time)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ResourceUsage {
+    pub memory: u64,
+    pub proportional_memory: u64,
+    pub unknown_proportional_memory: usize,
+    pub cpu: f32,
+    pub disk_read: u64,
+    pub disk_write: u64,
+}
+
+impl AddAssign for ResourceUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.memory += other.memory;
+        self.proportional_memory += other.proportional_memory;
+        self.unknown_proportional_memory += other.unknown_proportional_memory;
+        self.cpu += other.cpu;
+        self.disk_read += other.disk_read;
+        self.disk_write += other.disk_write;
+    }
+}
+
+pub fn refresh_processes(
+    system: &mut sysinfo::System,
+    proc_cache: &mut ProcCache,
+    read_memory_breakdown: bool,
+) {
+    system.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        sysinfo::ProcessRefreshKind::nothing()
+            .with_memory()
+            .with_cpu()
+            .with_disk_usage()
+            .with_exe(sysinfo::UpdateKind::OnlyIfNotSet)
+            .with_cmd(sysinfo::UpdateKind::OnlyIfNotSet)
+            .with_tasks(),
+    );
+    proc_cache.refresh(system, read_memory_breakdown);
 }
 
 pub fn prepare_processes(app: &app::App) -> Vec<ProcessInfo> {
-    let (system, user_input) = (app.system(), app.user_input());
-    let (Ok(system), Ok(user_input)) = (system.read(), user_input.read()) else {
+    let (system, proc_cache, user_input) = (app.system(), app.proc_cache(), app.user_input());
+    let (Ok(system), Ok(proc_cache), Ok(mut user_input)) =
+        (system.read(), proc_cache.read(), user_input.write())
+    else {
         return Vec::new();
     };
 
@@ -37,26 +162,82 @@ pub fn prepare_processes(app: &app::App) -> Vec<ProcessInfo> {
 
     filter_thread_processes(user_input.show_thread_processes(), &mut processes);
 
-    let mut processes_info = if user_input.hierarchical_view() {
-        let process_tree = ProcessTree::build(&processes);
-        let (processes, indentations) = process_tree.flattened();
-        extract_processes_info(&processes, indentations, &users, cpu_count)
-    } else {
-        let mut processes_info = extract_processes_info(
-            &processes,
-            vec![0_usize; processes.len()],
-            &users,
-            cpu_count,
+    let processes_info = extract_processes_info(&processes, &users, cpu_count, &proc_cache);
+    arrange_processes(processes_info, &mut user_input)
+}
+
+fn arrange_processes(
+    mut processes_info: Vec<ProcessInfo>,
+    user_input: &mut processes::UserInput,
+) -> Vec<ProcessInfo> {
+    let mut filter_query =
+        FilterQuery::parse(user_input.process_filter(), user_input.filter_options())
+            .unwrap_or(FilterQuery::All);
+    if user_input.show_column_filters() {
+        filter_query = filter_query.and(
+            user_input
+                .column_filters()
+                .query(user_input.filter_options()),
         );
-        user_input.sort_method().sort(&mut processes_info);
-        processes_info
-    };
+    }
 
-    filter_user_input(user_input.process_filter(), &mut processes_info);
+    if user_input.hierarchical_view() {
+        let mut process_tree = ProcessTree::build(processes_info);
+        if let Some(collapse_depth) = user_input.take_pending_collapse_depth() {
+            user_input.expand_all();
+            for key in process_tree.collapsible_keys(collapse_depth) {
+                user_input.set_collapsed(key, true);
+            }
+        }
+        if !matches!(filter_query, FilterQuery::All) {
+            process_tree.filter(
+                |process_info| filter_query.matches(process_info),
+                user_input.include_filter_descendants(),
+            );
+        }
+        process_tree.sort(
+            user_input.sort_method(),
+            user_input.rank_subtrees_by_usage(),
+        );
+        processes_info = process_tree.flattened(|key| user_input.is_collapsed(key));
+    } else {
+        processes_info.retain(|process_info| filter_query.matches(process_info));
+        user_input.sort_method().sort(&mut processes_info);
+    }
 
     processes_info
 }
 
+pub fn process_tree(system: &sysinfo::System) -> ProcessTree {
+    let mut processes: Vec<&sysinfo::Process> = system.processes().values().collect();
+    let users = sysinfo::Users::new_with_refreshed_list();
+    let cpu_count = system.cpus().len();
+
+    filter_thread_processes(false, &mut processes);
+
+    ProcessTree::build(extract_processes_info(
+        &processes,
+        &users,
+        cpu_count,
+        &ProcCache::default(),
+    ))
+}
+
+pub fn running_users(app: &app::App) -> BTreeSet<String> {
+    let system = app.system();
+    let Ok(system) = system.read() else {
+        return BTreeSet::new();
+    };
+    let users = sysinfo::Users::new_with_refreshed_list();
+
+    system
+        .processes()
+        .values()
+        .filter_map(|process| extract_user(process, &users))
+        .map(str::to_owned)
+        .collect()
+}
+
 fn filter_thread_processes(show_thread_processes: bool, processes: &mut Vec<&sysinfo::Process>) {
     if show_thread_processes {
         return;
@@ -65,14 +246,84 @@ fn filter_thread_processes(show_thread_processes: bool, processes: &mut Vec<&sys
     processes.retain(|process| process.thread_kind().is_none());
 }
 
-fn filter_user_input(process_filter: &str, processes_info: &mut Vec<ProcessInfo>) {
-    if process_filter.is_empty() {
-        return;
+#[cfg(test)]
+impl ProcessInfo {
+    pub(crate) fn for_test(id: u32) -> Self {
+        Self {
+            child_depth: 0,
+            tree_guides: Vec::new(),
+            has_children: false,
+            collapsed: false,
+            filter_context: false,
+            id,
+            parent_id: None,
+            session_id: None,
+            process_group: None,
+            tty: None,
+            threads: None,
+            name: format!("process-{id}"),
+            user: String::new(),
+            memory: 0,
+            virtual_memory: 0,
+            memory_breakdown: None,
+            cpu: 0.0,
+            nice: None,
+            disk_read: 0,
+            disk_write: 0,
+            path: String::new(),
+            command: String::new(),
+            status: sysinfo::ProcessStatus::Run,
+            start_time: 0,
+            run_time: 0,
+            subtree_usage: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProcessInfo, arrange_processes};
+    use crate::processes;
+    use std::collections::BTreeSet;
+
+    fn process_info(id: u32, parent_id: Option<u32>) -> ProcessInfo {
+        let mut process_info = ProcessInfo::for_test(id);
+        process_info.parent_id = parent_id;
+        process_info
+    }
+
+    fn arranged_pids(hierarchical_view: bool) -> Vec<u32> {
+        let processes_info = vec![
+            process_info(1, None),
+            process_info(2, Some(1)),
+            process_info(3, Some(2)),
+            process_info(4, Some(99)),
+            process_info(5, Some(5)),
+            process_info(6, Some(7)),
+            process_info(7, Some(6)),
+            process_info(8, Some(1)),
+        ];
+        let mut user_input = processes::UserInput::default();
+        *user_input.hierarchical_view_mut() = hierarchical_view;
+        arrange_processes(processes_info, &mut user_input)
+            .iter()
+            .map(|process_info| process_info.id)
+            .collect()
     }
 
-    processes_info.retain(|process_info| {
-        process_info.name.contains(process_filter)
-            || process_info.user.contains(process_filter)
-            || process_info.path.contains(process_filter)
-    });
+    #[test]
+    fn tree_and_flat_views_show_the_same_pids() {
+        let flat_pids = arranged_pids(false);
+        let tree_pids = arranged_pids(true);
+
+        assert_eq!(
+            tree_pids.len(),
+            flat_pids.len(),
+            "duplicate or missing PIDs"
+        );
+        assert_eq!(
+            tree_pids.into_iter().collect::<BTreeSet<_>>(),
+            flat_pids.into_iter().collect::<BTreeSet<_>>()
+        );
+    }
 }
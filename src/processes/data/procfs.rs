@@ -0,0 +1,206 @@
+use crate::processes::data;
+use std::collections::HashMap;
+use std::fs;
+
+const PROCESS_GROUP_FIELD: usize = 2;
+const TTY_FIELD: usize = 4;
+const NICE_FIELD: usize = 16;
+const THREADS_FIELD: usize = 17;
+const EXIT_CODE_FIELD: usize = 49;
+
+const PSEUDO_TERMINAL_MAJORS: std::ops::RangeInclusive<i32> = 136..=143;
+const TERMINAL_MAJOR: i32 = 4;
+const SERIAL_MINOR_OFFSET: i32 = 64;
+
+#[derive(Default)]
+pub struct ProcCache {
+    stats: HashMap<data::ProcessKey, ProcStat>,
+    memory_breakdowns: HashMap<data::ProcessKey, MemoryBreakdown>,
+}
+
+pub struct ProcStat {
+    pub process_group: u32,
+    pub tty_nr: i32,
+    pub nice: i32,
+    pub threads: usize,
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct MemoryBreakdown {
+    pub shared: u64,
+    pub private: u64,
+    pub proportional: u64,
+    pub swap: u64,
+}
+
+pub struct ProcessLimit {
+    pub name: String,
+    pub soft: String,
+    pub hard: String,
+    pub units: String,
+}
+
+pub struct MemoryMap {
+    pub address: String,
+    pub permissions: String,
+    pub offset: String,
+    pub device: String,
+    pub inode: String,
+    pub path: String,
+}
+
+impl ProcCache {
+    pub fn refresh(&mut self, system: &sysinfo::System, include_memory_breakdown: bool) {
+        self.stats = system
+            .processes()
+            .values()
+            .filter_map(|process| {
+                let stat = read_proc_stat(process.pid().as_u32())?;
+                Some((data::ProcessKey::from_process(process), stat))
+            })
+            .collect();
+        self.memory_breakdowns = if include_memory_breakdown {
+            system
+                .processes()
+                .values()
+                .filter(|process| process.thread_kind().is_none())
+                .filter_map(|process| {
+                    let breakdown = read_memory_breakdown(process.pid().as_u32())?;
+                    Some((data::ProcessKey::from_process(process), breakdown))
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+    }
+
+    pub fn stat(&self, process: &sysinfo::Process) -> Option<&ProcStat> {
+        self.stats.get(&data::ProcessKey::from_process(process))
+    }
+
+    pub fn memory_breakdown(&self, process: &sysinfo::Process) -> Option<MemoryBreakdown> {
+        self.memory_breakdowns
+            .get(&data::ProcessKey::from_process(process))
+            .copied()
+    }
+}
+
+pub fn read_proc_stat(pid: u32) -> Option<ProcStat> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let (_, fields) = stat.rsplit_once(')')?;
+    let fields: Vec<&str> = fields.split_whitespace().collect();
+
+    Some(ProcStat {
+        process_group: fields.get(PROCESS_GROUP_FIELD)?.parse().ok()?,
+        tty_nr: fields.get(TTY_FIELD)?.parse().ok()?,
+        nice: fields.get(NICE_FIELD)?.parse().ok()?,
+        threads: fields.get(THREADS_FIELD)?.parse().ok()?,
+        exit_code: fields
+            .get(EXIT_CODE_FIELD)
+            .and_then(|field| field.parse().ok()),
+    })
+}
+
+pub fn tty_name(tty_nr: i32) -> Option<String> {
+    if tty_nr == 0 {
+        return None;
+    }
+
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+    Some(if PSEUDO_TERMINAL_MAJORS.contains(&major) {
+        format!(
+            "pts/{}",
+            minor + (major - PSEUDO_TERMINAL_MAJORS.start()) * 256
+        )
+    } else if major == TERMINAL_MAJOR && minor < SERIAL_MINOR_OFFSET {
+        format!("tty{minor}")
+    } else if major == TERMINAL_MAJOR {
+        format!("ttyS{}", minor - SERIAL_MINOR_OFFSET)
+    } else {
+        format!("{major}:{minor}")
+    })
+}
+
+pub fn read_memory_breakdown(pid: u32) -> Option<MemoryBreakdown> {
+    let rollup = fs::read_to_string(format!("/proc/{pid}/smaps_rollup")).ok()?;
+    let mut breakdown = MemoryBreakdown::default();
+    let mut has_totals = false;
+    for line in rollup.lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(kibibytes) = value
+            .trim()
+            .strip_suffix("kB")
+            .and_then(|value| value.trim().parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let bytes = kibibytes * 1024;
+        match name {
+            "Pss" => {
+                breakdown.proportional = bytes;
+                has_totals = true;
+            }
+            "Shared_Clean" | "Shared_Dirty" => breakdown.shared += bytes,
+            "Private_Clean" | "Private_Dirty" => breakdown.private += bytes,
+            "Swap" => breakdown.swap = bytes,
+            _ => {}
+        }
+    }
+    has_totals.then_some(breakdown)
+}
+
+pub fn read_limits(pid: u32) -> Vec<ProcessLimit> {
+    let Ok(limits) = fs::read_to_string(format!("/proc/{pid}/limits")) else {
+        return Vec::new();
+    };
+    let mut lines = limits.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let (Some(soft_start), Some(hard_start), Some(units_start)) = (
+        header.find("Soft Limit"),
+        header.find("Hard Limit"),
+        header.find("Units"),
+    ) else {
+        return Vec::new();
+    };
+
+    let column = |line: &str, start: usize, end: usize| {
+        line.get(start..end.min(line.len()))
+            .unwrap_or_default()
+            .trim()
+            .to_owned()
+    };
+    lines
+        .map(|line| ProcessLimit {
+            name: column(line, 0, soft_start),
+            soft: column(line, soft_start, hard_start),
+            hard: column(line, hard_start, units_start),
+            units: column(line, units_start, line.len()),
+        })
+        .collect()
+}
+
+pub fn read_memory_maps(pid: u32) -> Vec<MemoryMap> {
+    let Ok(maps) = fs::read_to_string(format!("/proc/{pid}/maps")) else {
+        return Vec::new();
+    };
+
+    maps.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(MemoryMap {
+                address: fields.next()?.to_owned(),
+                permissions: fields.next()?.to_owned(),
+                offset: fields.next()?.to_owned(),
+                device: fields.next()?.to_owned(),
+                inode: fields.next()?.to_owned(),
+                path: fields.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
@@ -1,49 +1,236 @@
 use crate::processes::data;
+use std::cmp::Ordering;
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SortCategory {
+    Name,
     Id,
+    User,
     Memory,
     Cpu,
+    Nice,
     DiskRead,
     DiskWrite,
+    Path,
     Status,
+    StartTime,
+    RunTime,
+    Command,
+    ParentId,
+    Threads,
+    Session,
+    ProcessGroup,
+    Tty,
+    VirtualMemory,
+    SharedMemory,
+    PrivateMemory,
+    ProportionalMemory,
+    Swap,
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
+impl SortCategory {
+    pub const ALL: [Self; 23] = [
+        Self::Name,
+        Self::Id,
+        Self::User,
+        Self::Memory,
+        Self::Cpu,
+        Self::Nice,
+        Self::DiskRead,
+        Self::DiskWrite,
+        Self::Path,
+        Self::Status,
+        Self::StartTime,
+        Self::RunTime,
+        Self::Command,
+        Self::ParentId,
+        Self::Threads,
+        Self::Session,
+        Self::ProcessGroup,
+        Self::Tty,
+        Self::VirtualMemory,
+        Self::SharedMemory,
+        Self::PrivateMemory,
+        Self::ProportionalMemory,
+        Self::Swap,
+    ];
+    pub const MEMORY_BREAKDOWN: [Self; 4] = [
+        Self::SharedMemory,
+        Self::PrivateMemory,
+        Self::ProportionalMemory,
+        Self::Swap,
+    ];
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq)]
 pub enum SortDirection {
     Ascending,
     Descending,
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
-pub struct SortMethod {
+impl SortDirection {
+    fn toggled(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+
+    fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            Self::Ascending => ordering,
+            Self::Descending => ordering.reverse(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy)]
+pub struct SortKey {
     pub(crate) category: SortCategory,
     pub(crate) direction: SortDirection,
 }
 
-impl SortMethod {
-    pub fn sort(&self, processes_info: &mut [data::ProcessInfo]) {
-        match self.category {
-            SortCategory::Id => processes_info.sort_by(|a, b| a.id.cmp(&b.id)),
-            SortCategory::Memory => processes_info.sort_by(|a, b| a.memory.cmp(&b.memory)),
-            SortCategory::Cpu => processes_info.sort_by(|a, b| a.cpu.cmp(&b.cpu)),
-            SortCategory::DiskRead => processes_info.sort_by(|a, b| a.disk_read.cmp(&b.disk_read)),
-            SortCategory::DiskWrite => {
-                processes_info.sort_by(|a, b| a.disk_write.cmp(&b.disk_write));
-            }
-            SortCategory::Status => processes_info.sort_by(|a, b| a.status.cmp(&b.status)),
+impl SortKey {
+    fn compare(
+        self,
+        a: &data::ProcessInfo,
+        b: &data::ProcessInfo,
+        by_subtree_usage: bool,
+    ) -> Ordering {
+        let (a_usage, b_usage) = if by_subtree_usage {
+            (
+                a.subtree_usage.unwrap_or_else(|| a.resource_usage()),
+                b.subtree_usage.unwrap_or_else(|| b.resource_usage()),
+            )
+        } else {
+            (a.resource_usage(), b.resource_usage())
+        };
+
+        let ordering = match self.category {
+            SortCategory::Name => natural_cmp(&a.name, &b.name),
+            SortCategory::Id => a.id.cmp(&b.id),
+            SortCategory::User => natural_cmp(&a.user, &b.user),
+            SortCategory::Memory => a_usage.memory.cmp(&b_usage.memory),
+            SortCategory::Cpu => a_usage.cpu.total_cmp(&b_usage.cpu),
+            SortCategory::Nice => a.nice.cmp(&b.nice),
+            SortCategory::DiskRead => a_usage.disk_read.cmp(&b_usage.disk_read),
+            SortCategory::DiskWrite => a_usage.disk_write.cmp(&b_usage.disk_write),
+            SortCategory::Path => natural_cmp(&a.path, &b.path),
+            SortCategory::Status => status_rank(a.status).cmp(&status_rank(b.status)),
+            SortCategory::StartTime => a.start_time.cmp(&b.start_time),
+            SortCategory::RunTime => a.run_time.cmp(&b.run_time),
+            SortCategory::Command => natural_cmp(&a.command, &b.command),
+            SortCategory::ParentId => a.parent_id.cmp(&b.parent_id),
+            SortCategory::Threads => a.threads.cmp(&b.threads),
+            SortCategory::Session => a.session_id.cmp(&b.session_id),
+            SortCategory::ProcessGroup => a.process_group.cmp(&b.process_group),
+            SortCategory::Tty => a.tty.cmp(&b.tty),
+            SortCategory::VirtualMemory => a.virtual_memory.cmp(&b.virtual_memory),
+            SortCategory::SharedMemory => breakdown_cmp(a, b, |breakdown| breakdown.shared),
+            SortCategory::PrivateMemory => breakdown_cmp(a, b, |breakdown| breakdown.private),
+            SortCategory::ProportionalMemory => a_usage
+                .proportional_memory
+                .cmp(&b_usage.proportional_memory),
+            SortCategory::Swap => breakdown_cmp(a, b, |breakdown| breakdown.swap),
+        };
+        self.direction.apply(ordering)
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(from = "StoredSortMethod")]
+pub struct SortMethod {
+    keys: Vec<SortKey>,
+}
+
+#[derive(serde::Deserialize)]
+struct StoredSortMethod {
+    #[serde(default)]
+    keys: Vec<SortKey>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    category: Option<SortCategory>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    direction: Option<SortDirection>,
+}
+
+impl From<StoredSortMethod> for SortMethod {
+    fn from(stored: StoredSortMethod) -> Self {
+        if !stored.keys.is_empty() {
+            return Self { keys: stored.keys };
         }
 
-        if matches!(self.direction, SortDirection::Descending) {
-            processes_info.reverse();
+        match stored.category {
+            Some(category) => Self {
+                keys: vec![SortKey {
+                    category,
+                    direction: stored.direction.unwrap_or(SortDirection::Ascending),
+                }],
+            },
+            None => Self::default(),
         }
     }
+}
+
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
 
-    pub fn toggle_direction(&mut self) {
-        match self.direction {
-            SortDirection::Ascending => self.direction = SortDirection::Descending,
-            SortDirection::Descending => self.direction = SortDirection::Ascending,
+impl SortMethod {
+    pub fn sort(&self, processes_info: &mut [data::ProcessInfo]) {
+        processes_info.sort_by(|a, b| self.compare(a, b));
+    }
+
+    pub(crate) fn compare(&self, a: &data::ProcessInfo, b: &data::ProcessInfo) -> Ordering {
+        self.compare_ranked(a, b, false)
+    }
+
+    pub(crate) fn compare_ranked(
+        &self,
+        a: &data::ProcessInfo,
+        b: &data::ProcessInfo,
+        by_subtree_usage: bool,
+    ) -> Ordering {
+        self.keys
+            .iter()
+            .map(|key| key.compare(a, b, by_subtree_usage))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.id.cmp(&b.id))
+    }
+
+    pub(crate) fn key_position(&self, category: SortCategory) -> Option<(usize, SortKey)> {
+        self.keys
+            .iter()
+            .copied()
+            .enumerate()
+            .find(|(_, key)| key.category == category)
+    }
+
+    pub(crate) fn key_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn select(&mut self, category: SortCategory) {
+        let direction = match self.keys.first() {
+            Some(key) if key.category == category => key.direction.toggled(),
+            _ => SortDirection::Ascending,
+        };
+        self.keys = vec![SortKey {
+            category,
+            direction,
+        }];
+    }
+
+    pub fn add_secondary(&mut self, category: SortCategory) {
+        match self.keys.iter_mut().find(|key| key.category == category) {
+            Some(key) => key.direction = key.direction.toggled(),
+            None => self.keys.push(SortKey {
+                category,
+                direction: SortDirection::Ascending,
+            }),
         }
     }
 }
@@ -51,8 +238,118 @@ impl SortMethod {
 impl Default for SortMethod {
     fn default() -> Self {
         Self {
-            category: SortCategory::Cpu,
-            direction: SortDirection::Descending,
+            keys: vec![SortKey {
+                category: SortCategory::Cpu,
+                direction: SortDirection::Descending,
+            }],
         }
     }
 }
+
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_chars, mut b_chars) = (a.chars().peekable(), b.chars().peekable());
+
+    loop {
+        let (a_char, b_char) = match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(&a_char), Some(&b_char)) => (a_char, b_char),
+        };
+
+        if a_char.is_ascii_digit() && b_char.is_ascii_digit() {
+            let a_number = take_digits(&mut a_chars);
+            let b_number = take_digits(&mut b_chars);
+            let ordering = compare_digits(&a_number, &b_number);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            continue;
+        }
+
+        let ordering = a_char.to_lowercase().cmp(b_char.to_lowercase());
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        a_chars.next();
+        b_chars.next();
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    digits
+}
+
+fn compare_digits(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn breakdown_cmp(
+    a: &data::ProcessInfo,
+    b: &data::ProcessInfo,
+    value: impl Fn(data::MemoryBreakdown) -> u64,
+) -> Ordering {
+    a.memory_breakdown
+        .map(&value)
+        .cmp(&b.memory_breakdown.map(&value))
+}
+
+fn status_rank(status: sysinfo::ProcessStatus) -> u8 {
+    match status {
+        sysinfo::ProcessStatus::Run => 0,
+        sysinfo::ProcessStatus::Waking | sysinfo::ProcessStatus::Wakekill => 1,
+        sysinfo::ProcessStatus::Sleep => 2,
+        sysinfo::ProcessStatus::UninterruptibleDiskSleep | sysinfo::ProcessStatus::LockBlocked => 3,
+        sysinfo::ProcessStatus::Idle | sysinfo::ProcessStatus::Parked => 4,
+        sysinfo::ProcessStatus::Stop | sysinfo::ProcessStatus::Tracing => 5,
+        sysinfo::ProcessStatus::Zombie => 6,
+        sysinfo::ProcessStatus::Dead => 7,
+        sysinfo::ProcessStatus::Unknown(_) => 8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SortCategory, SortDirection, SortMethod};
+
+    fn single_key(sort_method: &SortMethod) -> Option<(SortCategory, SortDirection)> {
+        match sort_method.keys.as_slice() {
+            [key] => Some((key.category, key.direction)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn reads_single_key_format() {
+        let Ok(sort_method) =
+            ron::from_str::<SortMethod>("(category: Memory, direction: Ascending)")
+        else {
+            panic!("failed to read the single-key format");
+        };
+        assert!(single_key(&sort_method) == Some((SortCategory::Memory, SortDirection::Ascending)));
+    }
+
+    #[test]
+    fn reads_multi_key_format() {
+        let mut sort_method = SortMethod::default();
+        sort_method.select(SortCategory::User);
+        sort_method.add_secondary(SortCategory::Name);
+        let Ok(serialized) = ron::to_string(&sort_method) else {
+            panic!("failed to write the sort method");
+        };
+        let Ok(sort_method) = ron::from_str::<SortMethod>(&serialized) else {
+            panic!("failed to read {serialized:?}");
+        };
+        assert_eq!(sort_method.key_count(), 2);
+        assert!(
+            sort_method
+                .key_position(SortCategory::Name)
+                .is_some_and(|(index, _)| index == 1)
+        );
+    }
+}
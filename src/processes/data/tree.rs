@@ -1,65 +1,165 @@
-use std::collections::HashMap;
+use crate::processes::data;
+use std::collections::{HashMap, HashSet};
 
-struct Node<'a> {
-    process: &'a sysinfo::Process,
+struct Node {
+    info: data::ProcessInfo,
     children: Vec<Self>,
 }
 
-impl<'a> Node<'a> {
-    fn new(process: &'a sysinfo::Process) -> Self {
+impl Node {
+    fn new(info: data::ProcessInfo) -> Self {
         Self {
-            process,
+            info,
             children: Vec::new(),
         }
     }
 
-    fn flatten(
-        &'a self,
-        flat_list: &mut Vec<&'a sysinfo::Process>,
-        indentations: &mut Vec<usize>,
+    fn compute_subtree_usage(&mut self) -> data::ResourceUsage {
+        let mut subtree_usage = self.info.resource_usage();
+        for child in &mut self.children {
+            subtree_usage += child.compute_subtree_usage();
+        }
+        self.info.subtree_usage = Some(subtree_usage);
+        subtree_usage
+    }
+
+    fn sort(&mut self, sort_method: &data::SortMethod, by_subtree_usage: bool) {
+        sort_nodes(&mut self.children, sort_method, by_subtree_usage);
+    }
+
+    fn filter(
+        mut self,
+        matches: &impl Fn(&data::ProcessInfo) -> bool,
+        include_descendants: bool,
+    ) -> Option<Self> {
+        let is_match = matches(&self.info);
+        if is_match && include_descendants {
+            return Some(self);
+        }
+
+        self.children = filter_nodes(self.children, matches, include_descendants);
+        if !is_match && self.children.is_empty() {
+            return None;
+        }
+
+        self.info.filter_context = !is_match;
+        Some(self)
+    }
+
+    fn collect_collapsible_keys(
+        &self,
+        min_depth: usize,
         depth: usize,
+        keys: &mut Vec<data::ProcessKey>,
     ) {
-        flat_list.push(self.process);
-        indentations.push(depth);
+        if self.children.is_empty() {
+            return;
+        }
+        if depth >= min_depth {
+            keys.push(self.info.key());
+        }
+        for child in &self.children {
+            child.collect_collapsible_keys(min_depth, depth + 1, keys);
+        }
+    }
+
+    fn find(&self, pid: u32) -> Option<&Self> {
+        if self.info.id == pid {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(pid))
+    }
+
+    fn collect_pids_leaves_first(&self, pids: &mut Vec<u32>) {
         for child in &self.children {
-            child.flatten(flat_list, indentations, depth + 1);
+            child.collect_pids_leaves_first(pids);
         }
+        pids.push(self.info.id);
+    }
+
+    fn flatten(
+        self,
+        flat_list: &mut Vec<data::ProcessInfo>,
+        tree_guides: &mut Vec<bool>,
+        is_collapsed: &impl Fn(data::ProcessKey) -> bool,
+    ) {
+        let mut info = self.info;
+        info.child_depth = tree_guides.len();
+        info.tree_guides = tree_guides.clone();
+        info.has_children = !self.children.is_empty();
+        info.collapsed = info.has_children && !info.filter_context && is_collapsed(info.key());
+        let collapsed = info.collapsed;
+        flat_list.push(info);
+
+        if collapsed {
+            return;
+        }
+
+        let child_count = self.children.len();
+        for (index, child) in self.children.into_iter().enumerate() {
+            tree_guides.push(index + 1 < child_count);
+            child.flatten(flat_list, tree_guides, is_collapsed);
+            tree_guides.pop();
+        }
+    }
+}
+
+fn sort_nodes(nodes: &mut [Node], sort_method: &data::SortMethod, by_subtree_usage: bool) {
+    nodes.sort_by(|a, b| sort_method.compare_ranked(&a.info, &b.info, by_subtree_usage));
+    for node in nodes {
+        node.sort(sort_method, by_subtree_usage);
     }
 }
 
-pub struct ProcessTree<'a> {
-    roots: Vec<Node<'a>>,
+fn filter_nodes(
+    nodes: Vec<Node>,
+    matches: &impl Fn(&data::ProcessInfo) -> bool,
+    include_descendants: bool,
+) -> Vec<Node> {
+    nodes
+        .into_iter()
+        .filter_map(|node| node.filter(matches, include_descendants))
+        .collect()
 }
 
-impl<'a> ProcessTree<'a> {
-    pub(crate) fn build(processes: &[&'a sysinfo::Process]) -> Self {
-        let mut pid_to_node: HashMap<sysinfo::Pid, Node<'a>> = HashMap::new();
-        for process in processes {
-            pid_to_node.insert(process.pid(), Node::new(process));
-        }
+pub struct ProcessTree {
+    roots: Vec<Node>,
+}
 
-        let mut parent_to_children: HashMap<sysinfo::Pid, Vec<sysinfo::Pid>> = HashMap::new();
+impl ProcessTree {
+    pub(crate) fn build(processes_info: Vec<data::ProcessInfo>) -> Self {
+        let pids: HashSet<u32> = processes_info
+            .iter()
+            .map(|process_info| process_info.id)
+            .collect();
+
+        let mut parent_to_children: HashMap<u32, Vec<u32>> = HashMap::new();
         let mut root_pids = Vec::new();
 
-        for process in processes {
-            match process.parent() {
-                Some(parent_pid) => {
+        for process_info in &processes_info {
+            match process_info.parent_id {
+                Some(parent_pid) if parent_pid != process_info.id && pids.contains(&parent_pid) => {
                     parent_to_children
                         .entry(parent_pid)
                         .or_default()
-                        .push(process.pid());
+                        .push(process_info.id);
                 }
-                None => {
-                    root_pids.push(process.pid());
+                _ => {
+                    root_pids.push(process_info.id);
                 }
             }
         }
 
-        fn build_subtree<'a>(
-            pid: sysinfo::Pid,
-            pid_to_node: &mut HashMap<sysinfo::Pid, Node<'a>>,
-            parent_child_map: &HashMap<sysinfo::Pid, Vec<sysinfo::Pid>>,
-        ) -> Option<Node<'a>> {
+        let mut pid_to_node: HashMap<u32, Node> = processes_info
+            .into_iter()
+            .map(|process_info| (process_info.id, Node::new(process_info)))
+            .collect();
+
+        fn build_subtree(
+            pid: u32,
+            pid_to_node: &mut HashMap<u32, Node>,
+            parent_child_map: &HashMap<u32, Vec<u32>>,
+        ) -> Option<Node> {
             let mut node = pid_to_node.remove(&pid)?;
 
             if let Some(child_pids) = parent_child_map.get(&pid) {
@@ -83,15 +183,116 @@ impl<'a> ProcessTree<'a> {
             }
         }
 
+        while let Some(cycle_pid) = pid_to_node.keys().min().copied() {
+            if let Some(cycle_node) =
+                build_subtree(cycle_pid, &mut pid_to_node, &parent_to_children)
+            {
+                roots.push(cycle_node);
+            }
+        }
+
+        for root_node in &mut roots {
+            root_node.compute_subtree_usage();
+        }
+
         Self { roots }
     }
 
-    pub(crate) fn flattened(&'a self) -> (Vec<&'a sysinfo::Process>, Vec<usize>) {
-        let mut flat_list = Vec::new();
-        let mut indentations = Vec::new();
+    pub(crate) fn filter(
+        &mut self,
+        matches: impl Fn(&data::ProcessInfo) -> bool,
+        include_descendants: bool,
+    ) {
+        let roots = std::mem::take(&mut self.roots);
+        self.roots = filter_nodes(roots, &matches, include_descendants);
+    }
+
+    pub(crate) fn sort(&mut self, sort_method: &data::SortMethod, by_subtree_usage: bool) {
+        sort_nodes(&mut self.roots, sort_method, by_subtree_usage);
+    }
+
+    pub(crate) fn collapsible_keys(&self, min_depth: usize) -> Vec<data::ProcessKey> {
+        let mut keys = Vec::new();
         for root_node in &self.roots {
-            root_node.flatten(&mut flat_list, &mut indentations, 0);
+            root_node.collect_collapsible_keys(min_depth, 0, &mut keys);
+        }
+        keys
+    }
+
+    pub(crate) fn subtree_pids(&self, pid: u32) -> Vec<u32> {
+        let mut pids = Vec::new();
+        if let Some(node) = self.roots.iter().find_map(|root_node| root_node.find(pid)) {
+            node.collect_pids_leaves_first(&mut pids);
         }
-        (flat_list, indentations)
+        pids
+    }
+
+    pub(crate) fn flattened(
+        self,
+        is_collapsed: impl Fn(data::ProcessKey) -> bool,
+    ) -> Vec<data::ProcessInfo> {
+        let mut flat_list = Vec::new();
+        let mut tree_guides = Vec::new();
+        for root_node in self.roots {
+            root_node.flatten(&mut flat_list, &mut tree_guides, &is_collapsed);
+        }
+        flat_list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProcessTree;
+    use crate::processes::data;
+    use std::collections::BTreeSet;
+
+    fn process_info(id: u32, parent_id: Option<u32>) -> data::ProcessInfo {
+        let mut process_info = data::ProcessInfo::for_test(id);
+        process_info.parent_id = parent_id;
+        process_info
+    }
+
+    fn assert_same_pids(processes_info: Vec<data::ProcessInfo>) {
+        let expected: BTreeSet<u32> = processes_info
+            .iter()
+            .map(|process_info| process_info.id)
+            .collect();
+        let flattened = ProcessTree::build(processes_info).flattened(|_| false);
+        let actual: Vec<u32> = flattened
+            .iter()
+            .map(|process_info| process_info.id)
+            .collect();
+
+        assert_eq!(actual.len(), expected.len(), "duplicate or missing PIDs");
+        assert_eq!(actual.into_iter().collect::<BTreeSet<_>>(), expected);
+    }
+
+    #[test]
+    fn missing_parent_becomes_root() {
+        assert_same_pids(vec![
+            process_info(1, None),
+            process_info(2, Some(1)),
+            process_info(3, Some(99)),
+            process_info(4, Some(3)),
+        ]);
+    }
+
+    #[test]
+    fn self_parent_becomes_root() {
+        assert_same_pids(vec![
+            process_info(1, None),
+            process_info(5, Some(5)),
+            process_info(6, Some(5)),
+        ]);
+    }
+
+    #[test]
+    fn two_node_cycle_is_kept() {
+        assert_same_pids(vec![
+            process_info(1, None),
+            process_info(7, Some(8)),
+            process_info(8, Some(7)),
+            process_info(9, Some(8)),
+        ]);
     }
 }
@@ -1,6 +1,22 @@
+mod control;
 mod data;
 mod ui;
 
+pub use control::Escalation;
+pub use control::ProtectedProcesses;
+pub use control::ProtectionMode;
+pub use control::SignalReport;
+pub use control::Termination;
+pub use control::TreeSignalOrder;
+pub use control::check_terminations;
+pub use control::signal_from_name;
+pub use control::signal_name;
+pub use data::ColumnFilters;
+pub use data::FilterOptions;
+pub use data::ProcCache;
+pub use data::ProcessKey;
+pub use data::SortCategory;
 pub use data::SortMethod;
+pub use data::refresh_processes;
 pub use ui::UserInput;
 pub use ui::update;
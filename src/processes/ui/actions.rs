@@ -0,0 +1,489 @@
+use crate::processes::ui::{confirmation, details};
+use crate::processes::{self, UserInput, control, data};
+
+const SIGNAL_MENU_TEXT: &str = "Signals";
+const QUICK_SIGNAL_SYMBOL: &str = "★";
+const DETAILS_TEXT: &str = "Details";
+const COPY_INFO_TEXT: &str = "Copy Info";
+const RENICE_TEXT: &str = "Renice";
+const NICE_TEXT: &str = "Nice";
+const APPLY_TEXT: &str = "Apply";
+const SUSPEND_TEXT: &str = "Suspend";
+const RESUME_TEXT: &str = "Resume";
+const SUSPEND_SUBTREE_TEXT: &str = "Include Children";
+const TERMINATE_TREE_TEXT: &str = "Terminate Tree";
+const KILL_TREE_TEXT: &str = "Kill Tree";
+const STOP_TEXT: &str = "Stop";
+const KILL_NOW_TEXT: &str = "Kill Now";
+const DISMISS_TEXT: &str = "Dismiss";
+const TERMINATION_SETTINGS_SYMBOL: &str = "⚙";
+const ESCALATE_TEXT: &str = "Escalate Trees to Kill";
+const CONFIRM_ESCALATION_TEXT: &str = "Ask Before Killing";
+const GRACE_PERIOD_TEXT: &str = "Grace Period";
+const GRACE_PERIOD_RANGE: std::ops::RangeInclusive<u64> = 1..=600;
+const CONFIRM_DESTRUCTIVE_TEXT: &str = "Confirm Destructive Actions";
+const PROTECTED_MENU_TEXT: &str = "Protected Processes";
+const PROTECT_INIT_TEXT: &str = "Init (PID 1)";
+const PROTECT_DISPLAY_SERVER_TEXT: &str = "Display Server";
+const PROTECT_SELF_TEXT: &str = "This Application";
+const NAME_PATTERNS_HINT: &str = "One name pattern per line, * matches anything";
+
+#[derive(Clone, Copy)]
+pub(crate) enum ProcessAction {
+    Signal {
+        signal: sysinfo::Signal,
+        include_subtree: bool,
+    },
+    Stop,
+    Tree(sysinfo::Signal),
+}
+
+impl ProcessAction {
+    fn signal(signal: sysinfo::Signal) -> Self {
+        Self::Signal {
+            signal,
+            include_subtree: false,
+        }
+    }
+
+    fn sent_signal(self) -> sysinfo::Signal {
+        match self {
+            Self::Signal { signal, .. } | Self::Tree(signal) => signal,
+            Self::Stop => sysinfo::Signal::Term,
+        }
+    }
+
+    pub(super) fn description(self) -> String {
+        match self {
+            Self::Signal {
+                signal,
+                include_subtree: false,
+            } => format!("Send {}", control::signal_name(signal)),
+            Self::Signal {
+                signal,
+                include_subtree: true,
+            } => format!("Send {} to Subtree", control::signal_name(signal)),
+            Self::Stop => STOP_TEXT.to_owned(),
+            Self::Tree(sysinfo::Signal::Kill) => KILL_TREE_TEXT.to_owned(),
+            Self::Tree(signal) => format!("Send {} to Tree", control::signal_name(signal)),
+        }
+    }
+
+    fn targets(self, system: &sysinfo::System, pids: &[u32]) -> Vec<u32> {
+        let include_subtree = match self {
+            Self::Signal {
+                include_subtree, ..
+            } => include_subtree,
+            Self::Stop => false,
+            Self::Tree(_) => true,
+        };
+        control::signal_targets(system, pids, include_subtree)
+    }
+}
+
+pub(super) fn quick_signal_buttons(
+    system: &sysinfo::System,
+    pids: &[u32],
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    for signal in user_input.quick_signals() {
+        if ui.button(control::signal_description(signal)).clicked() {
+            request_action(system, pids, ProcessAction::signal(signal), user_input);
+            ui.close();
+        }
+    }
+}
+
+pub(super) fn signal_menu_button(
+    system: &sysinfo::System,
+    pids: &[u32],
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    ui.menu_button(SIGNAL_MENU_TEXT, |ui| {
+        for signal in control::supported_signals() {
+            ui.horizontal(|ui| {
+                let mut quick = user_input.is_quick_signal(signal);
+                if ui
+                    .toggle_value(&mut quick, QUICK_SIGNAL_SYMBOL)
+                    .on_hover_text("Show as a quick button")
+                    .changed()
+                {
+                    user_input.set_quick_signal(signal, quick);
+                }
+
+                let text = format!(
+                    "{} ({})",
+                    control::signal_name(signal),
+                    control::signal_description(signal)
+                );
+                if ui.button(text).clicked() {
+                    request_action(system, pids, ProcessAction::signal(signal), user_input);
+                    ui.close();
+                }
+            });
+        }
+    });
+}
+
+pub(super) fn suspend_controls(
+    system: &sysinfo::System,
+    pids: &[u32],
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    let (stopped, running): (Vec<&sysinfo::Process>, Vec<&sysinfo::Process>) = pids
+        .iter()
+        .filter_map(|&pid| system.process(sysinfo::Pid::from_u32(pid)))
+        .partition(|process| data::extract_status(process) == sysinfo::ProcessStatus::Stop);
+
+    let suspend_clicked = !running.is_empty() && ui.button(SUSPEND_TEXT).clicked();
+    let resume_clicked = !stopped.is_empty() && ui.button(RESUME_TEXT).clicked();
+    ui.checkbox(user_input.suspend_subtree_mut(), SUSPEND_SUBTREE_TEXT);
+
+    let requested_signal = if suspend_clicked {
+        Some(sysinfo::Signal::Stop)
+    } else if resume_clicked {
+        Some(sysinfo::Signal::Continue)
+    } else {
+        None
+    };
+    if let Some(signal) = requested_signal {
+        let action = ProcessAction::Signal {
+            signal,
+            include_subtree: user_input.suspend_subtree(),
+        };
+        request_action(system, pids, action, user_input);
+        ui.close();
+    }
+}
+
+pub(super) fn renice_menu_button(pids: &[u32], user_input: &mut UserInput, ui: &mut egui::Ui) {
+    ui.menu_button(RENICE_TEXT, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(NICE_TEXT);
+            ui.add(egui::DragValue::new(user_input.renice_value_mut()).range(control::NICE_RANGE));
+        });
+        if ui.button(APPLY_TEXT).clicked() {
+            renice(pids, user_input);
+            ui.close();
+        }
+    });
+}
+
+fn renice(pids: &[u32], user_input: &mut UserInput) {
+    let nice = user_input.renice_value();
+    let results: Vec<(u32, Result<(), _>)> = pids
+        .iter()
+        .map(|&pid| (pid, control::renice(pid, nice)))
+        .collect();
+
+    let message = match results.as_slice() {
+        [(pid, Ok(()))] => format!("Set nice of {pid} to {nice}"),
+        [(pid, Err(error))] => format!("Renicing {pid} failed: {error}"),
+        results => format!(
+            "Set nice to {nice} for {} of {} processes",
+            results.iter().filter(|(_, result)| result.is_ok()).count(),
+            results.len()
+        ),
+    };
+    let details = results
+        .iter()
+        .map(|(pid, result)| match result {
+            Ok(()) => format!("{pid}: nice set to {nice}"),
+            Err(error) => format!("{pid}: {error}"),
+        })
+        .collect();
+    user_input.set_action_details(message, details);
+}
+
+pub(super) fn stop_button(
+    system: &sysinfo::System,
+    pids: &[u32],
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    if ui
+        .button(STOP_TEXT)
+        .on_hover_text("Terminate, then kill if still running after the grace period")
+        .clicked()
+    {
+        request_action(system, pids, ProcessAction::Stop, user_input);
+        ui.close();
+    }
+}
+
+pub(super) fn details_button(
+    system: &sysinfo::System,
+    pid: u32,
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    if ui.button(DETAILS_TEXT).clicked() {
+        details::open_details(system, pid, user_input);
+        ui.close();
+    }
+}
+
+pub(super) fn copy_info_button(system: &sysinfo::System, pids: &[u32], ui: &mut egui::Ui) {
+    if ui
+        .button(COPY_INFO_TEXT)
+        .on_hover_text("Copy PID, name, user, path and command line of each process")
+        .clicked()
+    {
+        let users = sysinfo::Users::new_with_refreshed_list();
+        let lines: Vec<String> = pids
+            .iter()
+            .filter_map(|&pid| system.process(sysinfo::Pid::from_u32(pid)))
+            .map(|process| {
+                [
+                    process.pid().to_string(),
+                    data::extract_name(process).unwrap_or_default().to_owned(),
+                    data::extract_user(process, &users)
+                        .unwrap_or_default()
+                        .to_owned(),
+                    data::extract_path(process).unwrap_or_default().to_owned(),
+                    data::extract_command(process),
+                ]
+                .join("\t")
+            })
+            .collect();
+        ui.ctx().copy_text(lines.join("\n"));
+        ui.close();
+    }
+}
+
+pub(super) fn termination_status(
+    system: &sysinfo::System,
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    let mut kill_index = None;
+    let mut dismiss_index = None;
+
+    for (index, termination) in user_input.terminations().iter().enumerate() {
+        if termination.awaiting_confirmation() {
+            ui.label(format!("{} is still running", termination.target()));
+            if ui.button(KILL_NOW_TEXT).clicked() {
+                kill_index = Some(index);
+            }
+            if ui.button(DISMISS_TEXT).clicked() {
+                dismiss_index = Some(index);
+            }
+        } else {
+            ui.label(format!(
+                "Stopping {}: {}s",
+                termination.target(),
+                termination.remaining().as_secs()
+            ));
+        }
+        ui.separator();
+    }
+
+    if let Some(index) = kill_index {
+        user_input.kill_termination_survivors(index, system);
+    }
+    if let Some(index) = dismiss_index {
+        user_input.dismiss_termination(index);
+    }
+}
+
+pub(super) fn tree_controls(
+    system: &sysinfo::System,
+    pids: &[u32],
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    if ui.button(TERMINATE_TREE_TEXT).clicked() {
+        let action = ProcessAction::Tree(sysinfo::Signal::Term);
+        request_action(system, pids, action, user_input);
+        ui.close();
+    }
+    if ui.button(KILL_TREE_TEXT).clicked() {
+        let action = ProcessAction::Tree(sysinfo::Signal::Kill);
+        request_action(system, pids, action, user_input);
+        ui.close();
+    }
+
+    termination_settings_button(user_input, ui);
+}
+
+fn termination_settings_button(user_input: &mut UserInput, ui: &mut egui::Ui) {
+    ui.menu_button(TERMINATION_SETTINGS_SYMBOL, |ui| {
+        for order in processes::TreeSignalOrder::ALL {
+            ui.radio_value(user_input.tree_signal_order_mut(), order, order.label());
+        }
+        ui.separator();
+        ui.checkbox(user_input.escalate_tree_termination_mut(), ESCALATE_TEXT);
+        ui.checkbox(user_input.confirm_escalation_mut(), CONFIRM_ESCALATION_TEXT);
+        ui.horizontal(|ui| {
+            ui.label(GRACE_PERIOD_TEXT);
+            ui.add(
+                egui::DragValue::new(user_input.grace_period_secs_mut())
+                    .range(GRACE_PERIOD_RANGE)
+                    .suffix(" s"),
+            );
+        });
+        ui.separator();
+        ui.checkbox(
+            user_input.confirm_destructive_actions_mut(),
+            CONFIRM_DESTRUCTIVE_TEXT,
+        );
+        ui.menu_button(PROTECTED_MENU_TEXT, |ui| {
+            protected_processes_menu(user_input.protected_processes_mut(), ui);
+        });
+    });
+}
+
+fn protected_processes_menu(protected: &mut processes::ProtectedProcesses, ui: &mut egui::Ui) {
+    ui.checkbox(&mut protected.protect_init, PROTECT_INIT_TEXT);
+    ui.checkbox(
+        &mut protected.protect_display_server,
+        PROTECT_DISPLAY_SERVER_TEXT,
+    );
+    ui.checkbox(&mut protected.protect_self, PROTECT_SELF_TEXT);
+    ui.add(
+        egui::TextEdit::multiline(&mut protected.name_patterns)
+            .hint_text(NAME_PATTERNS_HINT)
+            .desired_rows(3),
+    );
+    ui.separator();
+    for mode in processes::ProtectionMode::ALL {
+        ui.radio_value(&mut protected.mode, mode, mode.label());
+    }
+}
+
+pub(super) fn process_actions(
+    system: &sysinfo::System,
+    pids: &[u32],
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    stop_button(system, pids, user_input, ui);
+    quick_signal_buttons(system, pids, user_input, ui);
+    signal_menu_button(system, pids, user_input, ui);
+    ui.separator();
+    suspend_controls(system, pids, user_input, ui);
+    renice_menu_button(pids, user_input, ui);
+    ui.separator();
+    tree_controls(system, pids, user_input, ui);
+}
+
+pub(super) fn process_context_menu(
+    system: &sysinfo::System,
+    pid: u32,
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    let pids = if user_input.is_selected(pid) {
+        user_input.selected_pids()
+    } else {
+        vec![pid]
+    };
+    if pids.len() > 1 {
+        ui.label(format!("{} selected processes", pids.len()));
+        ui.separator();
+    } else if system.process(sysinfo::Pid::from_u32(pid)).is_none() {
+        ui.label("Process has exited");
+        return;
+    }
+
+    process_actions(system, &pids, user_input, ui);
+    ui.separator();
+    if let [pid] = pids.as_slice() {
+        details_button(system, *pid, user_input, ui);
+    }
+    copy_info_button(system, &pids, ui);
+}
+
+fn request_action(
+    system: &sysinfo::System,
+    pids: &[u32],
+    action: ProcessAction,
+    user_input: &mut UserInput,
+) {
+    let signal = action.sent_signal();
+    if control::is_harmless(signal) {
+        perform_action(system, pids, action, user_input);
+        return;
+    }
+
+    let targets = action.targets(system, pids);
+    if control::is_suspending(signal) && targets.contains(&std::process::id()) {
+        user_input.set_action_message(format!(
+            "Refused to {}: this application cannot be suspended",
+            action.description().to_lowercase()
+        ));
+        return;
+    }
+
+    let protected = user_input
+        .protected_processes()
+        .protected_processes(system, &targets);
+
+    if !protected.is_empty()
+        && user_input.protected_processes().mode == processes::ProtectionMode::Refuse
+    {
+        let protected_list: Vec<String> = protected
+            .iter()
+            .map(|(pid, reason)| format!("{pid} ({reason})"))
+            .collect();
+        user_input.set_action_message(format!(
+            "Refused to {}: protected {}",
+            action.description().to_lowercase(),
+            protected_list.join(", ")
+        ));
+        return;
+    }
+
+    if protected.is_empty()
+        && (!control::is_destructive(signal) || !user_input.confirm_destructive_actions())
+    {
+        perform_action(system, pids, action, user_input);
+        return;
+    }
+
+    let child_count = match pids {
+        [pid] => data::process_tree(system)
+            .subtree_pids(*pid)
+            .len()
+            .saturating_sub(1),
+        _ => targets.len().saturating_sub(pids.len()),
+    };
+    user_input.set_pending_confirmation(confirmation::PendingConfirmation::new(
+        pids.to_vec(),
+        action,
+        child_count,
+        protected,
+    ));
+}
+
+pub(super) fn perform_action(
+    system: &sysinfo::System,
+    pids: &[u32],
+    action: ProcessAction,
+    user_input: &mut UserInput,
+) {
+    let report = match action {
+        ProcessAction::Signal { signal, .. } => {
+            control::send_signal_to_all(system, &action.targets(system, pids), signal)
+        }
+        ProcessAction::Stop | ProcessAction::Tree(_) => {
+            let mut reports = pids.iter().map(|&pid| match action {
+                ProcessAction::Tree(signal) => {
+                    user_input.start_tree_termination(system, pid, signal)
+                }
+                _ => user_input.start_stop(system, pid),
+            });
+            let Some(mut report) = reports.next() else {
+                return;
+            };
+            for other in reports {
+                report.extend(other);
+            }
+            report
+        }
+    };
+    user_input.set_action_report(&report);
+}
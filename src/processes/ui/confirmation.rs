@@ -0,0 +1,173 @@
+use crate::app;
+use crate::processes::data;
+use crate::processes::ui::actions;
+
+const CONFIRMATION_MODAL_ID: &str = "confirm_process_action";
+const CONFIRM_TEXT: &str = "Confirm";
+const CANCEL_TEXT: &str = "Cancel";
+const ACKNOWLEDGE_PROTECTED_TEXT: &str = "I understand that this affects protected processes";
+const EXITED_TEXT: &str = "The process has exited.";
+const BLANK_DETAIL: &str = "-";
+const MAX_LISTED_PROCESSES: usize = 20;
+
+pub(crate) struct PendingConfirmation {
+    pids: Vec<u32>,
+    action: actions::ProcessAction,
+    child_count: usize,
+    protected: Vec<(u32, &'static str)>,
+    acknowledged: bool,
+}
+
+impl PendingConfirmation {
+    pub(crate) fn new(
+        pids: Vec<u32>,
+        action: actions::ProcessAction,
+        child_count: usize,
+        protected: Vec<(u32, &'static str)>,
+    ) -> Self {
+        Self {
+            pids,
+            action,
+            child_count,
+            protected,
+            acknowledged: false,
+        }
+    }
+
+    fn can_confirm(&self) -> bool {
+        self.protected.is_empty() || self.acknowledged
+    }
+}
+
+enum Outcome {
+    Confirmed,
+    Cancelled,
+}
+
+pub fn update_confirmation(app: &app::App, ctx: &egui::Context) {
+    let (system, user_input) = (app.system(), app.user_input());
+    let (Ok(system), Ok(mut user_input)) = (system.read(), user_input.write()) else {
+        return;
+    };
+    let Some(mut pending) = user_input.take_pending_confirmation() else {
+        return;
+    };
+
+    let processes: Vec<&sysinfo::Process> = pending
+        .pids
+        .iter()
+        .filter_map(|&pid| system.process(sysinfo::Pid::from_u32(pid)))
+        .collect();
+    let modal = egui::Modal::new(egui::Id::new(CONFIRMATION_MODAL_ID)).show(ctx, |ui| {
+        ui.heading(format!("{}?", pending.action.description()));
+        ui.separator();
+
+        match processes.as_slice() {
+            [] => {
+                ui.label(EXITED_TEXT);
+                return ui
+                    .button(CANCEL_TEXT)
+                    .clicked()
+                    .then_some(Outcome::Cancelled);
+            }
+            [process] => process_details(process, pending.child_count, ui),
+            processes => process_list(processes, pending.child_count, ui),
+        }
+
+        if !pending.protected.is_empty() {
+            ui.separator();
+            for (pid, reason) in &pending.protected {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    format!("{pid} is protected: {reason}"),
+                );
+            }
+            ui.checkbox(&mut pending.acknowledged, ACKNOWLEDGE_PROTECTED_TEXT);
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(pending.can_confirm(), egui::Button::new(CONFIRM_TEXT))
+                .clicked()
+            {
+                Some(Outcome::Confirmed)
+            } else if ui.button(CANCEL_TEXT).clicked() {
+                Some(Outcome::Cancelled)
+            } else {
+                None
+            }
+        })
+        .inner
+    });
+
+    match modal.inner {
+        Some(Outcome::Confirmed) => {
+            actions::perform_action(&system, &pending.pids, pending.action, &mut user_input);
+        }
+        Some(Outcome::Cancelled) => {}
+        None if modal.should_close() => {}
+        None => user_input.set_pending_confirmation(pending),
+    }
+}
+
+fn process_details(process: &sysinfo::Process, child_count: usize, ui: &mut egui::Ui) {
+    let users = sysinfo::Users::new_with_refreshed_list();
+    let details = [
+        (
+            "Name",
+            data::extract_name(process)
+                .unwrap_or(BLANK_DETAIL)
+                .to_owned(),
+        ),
+        ("PID", process.pid().to_string()),
+        (
+            "User",
+            data::extract_user(process, &users)
+                .unwrap_or(BLANK_DETAIL)
+                .to_owned(),
+        ),
+        ("Command Line", data::extract_command(process)),
+        ("Children", child_count.to_string()),
+    ];
+
+    egui::Grid::new("confirmation_details")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (label, value) in details {
+                ui.strong(label);
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+}
+
+fn process_list(processes: &[&sysinfo::Process], child_count: usize, ui: &mut egui::Ui) {
+    let users = sysinfo::Users::new_with_refreshed_list();
+
+    egui::Grid::new("confirmation_process_list")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("PID");
+            ui.strong("Name");
+            ui.strong("User");
+            ui.end_row();
+            for process in processes.iter().take(MAX_LISTED_PROCESSES) {
+                ui.label(process.pid().to_string());
+                ui.label(data::extract_name(process).unwrap_or(BLANK_DETAIL));
+                ui.label(data::extract_user(process, &users).unwrap_or(BLANK_DETAIL));
+                ui.end_row();
+            }
+        });
+
+    if let Some(hidden_count) = processes.len().checked_sub(MAX_LISTED_PROCESSES)
+        && hidden_count > 0
+    {
+        ui.label(format!("and {hidden_count} more"));
+    }
+    ui.label(format!(
+        "{} selected processes, {child_count} additional descendants",
+        processes.len()
+    ));
+}
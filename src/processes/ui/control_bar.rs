@@ -0,0 +1,101 @@
+use crate::app;
+use crate::processes::data;
+use crate::processes::ui::actions;
+
+const CLIPBOARD_SYMBOL: &str = "📋";
+const CLEAR_SELECTION_TEXT: &str = "Clear Selection";
+const ACTION_DETAILS_TEXT: &str = "Details";
+
+const BLANK_PROCESS_PATH: &str = "";
+const BLANK_PROCESS_NAME: &str = "";
+
+pub fn update_control_bar(app: &app::App, ctx: &egui::Context, ui: &mut egui::Ui) {
+    let (system, user_input) = (app.system(), app.user_input());
+    let (Ok(system), Ok(mut user_input)) = (system.read(), user_input.write()) else {
+        return;
+    };
+
+    let process = user_input
+        .selected_pid()
+        .and_then(|pid| system.process(sysinfo::Pid::from_u32(pid)));
+    let selected_pids = user_input.selected_pids();
+
+    ui.horizontal_centered(|ui| {
+        if let Some(process) = process {
+            actions::process_actions(&system, &selected_pids, &mut user_input, ui);
+            ui.separator();
+            actions::details_button(&system, process.pid().as_u32(), &mut user_input, ui);
+            ui.separator();
+            process_labels(process, ctx, ui);
+        } else if selected_pids.len() > 1 {
+            ui.label(format!("{} selected", selected_pids.len()));
+            if ui.button(CLEAR_SELECTION_TEXT).clicked() {
+                user_input.clear_selection();
+            }
+            ui.separator();
+            actions::process_actions(&system, &selected_pids, &mut user_input, ui);
+            ui.separator();
+            actions::copy_info_button(&system, &selected_pids, ui);
+            ui.separator();
+        }
+
+        actions::termination_status(&system, &mut user_input, ui);
+
+        if let Some(message) = user_input.action_message() {
+            ui.label(message);
+        }
+        if user_input.action_details().len() > 1 {
+            ui.menu_button(ACTION_DETAILS_TEXT, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for detail in user_input.action_details() {
+                        ui.label(detail);
+                    }
+                });
+            });
+        }
+    });
+
+    ui.separator();
+}
+
+fn process_labels(process: &sysinfo::Process, ctx: &egui::Context, ui: &mut egui::Ui) {
+    if let Some(name) = data::extract_name(process) {
+        if ui.button(CLIPBOARD_SYMBOL).clicked() {
+            ctx.copy_text(
+                data::extract_name(process)
+                    .unwrap_or(BLANK_PROCESS_NAME)
+                    .to_owned(),
+            );
+        }
+        ui.label(name);
+        ui.separator();
+    }
+
+    let pid = process.pid().as_u32();
+    if ui.button(CLIPBOARD_SYMBOL).clicked() {
+        ctx.copy_text(pid.to_string());
+    }
+    ui.label(pid.to_string());
+
+    ui.separator();
+
+    if let Some(path) = data::extract_path(process) {
+        if ui.button(CLIPBOARD_SYMBOL).clicked() {
+            ctx.copy_text(
+                data::extract_path(process)
+                    .unwrap_or(BLANK_PROCESS_PATH)
+                    .to_owned(),
+            );
+        }
+        ui.label(path);
+        ui.separator();
+    }
+
+    let command = data::extract_command(process);
+    if !command.is_empty() {
+        if ui.button(CLIPBOARD_SYMBOL).clicked() {
+            ctx.copy_text(command.clone());
+        }
+        ui.add(egui::Label::new(command).truncate());
+    }
+}
@@ -0,0 +1,516 @@
+use crate::app;
+use crate::processes::ui::{
+    UNKNOWN_VALUE, format_breakdown, format_bytes, format_cpu, format_duration, format_optional,
+    format_status, format_timestamp,
+};
+use crate::processes::{UserInput, control, data};
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+const DETAILS_WINDOW_ID: &str = "process_details";
+const DETAILS_WINDOW_SIZE: [f32; 2] = [640.0, 420.0];
+const DETAILS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const DETAILS_ROW_HEIGHT: f32 = 18.0;
+const IO_PRIORITY_HEADING: &str = "I/O Priority";
+const IO_CLASS_TEXT: &str = "Class";
+const IO_LEVEL_TEXT: &str = "Level";
+const AFFINITY_HEADING: &str = "CPU Affinity";
+const ALL_CPUS_TEXT: &str = "All";
+const NO_CPUS_TEXT: &str = "None";
+const APPLY_TEXT: &str = "Apply";
+const EXITED_TEXT: &str = "The process has exited; showing its last known details.";
+const CPUS_PER_ROW: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DetailsTab {
+    General,
+    Environment,
+    Limits,
+    MemoryMaps,
+    Threads,
+    Scheduling,
+}
+
+impl DetailsTab {
+    const ALL: [Self; 6] = [
+        Self::General,
+        Self::Environment,
+        Self::Limits,
+        Self::MemoryMaps,
+        Self::Threads,
+        Self::Scheduling,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::General => "General",
+            Self::Environment => "Environment",
+            Self::Limits => "Limits",
+            Self::MemoryMaps => "Memory Maps",
+            Self::Threads => "Threads",
+            Self::Scheduling => "Scheduling",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Snapshot {
+    general: Vec<(&'static str, String)>,
+    environment: Vec<String>,
+    limits: Vec<data::ProcessLimit>,
+    memory_maps: Vec<String>,
+    threads: Vec<[String; 4]>,
+}
+
+impl Snapshot {
+    fn capture(system: &sysinfo::System, process: &sysinfo::Process) -> Self {
+        let pid = process.pid().as_u32();
+        let users = sysinfo::Users::new_with_refreshed_list();
+        let stat = data::read_proc_stat(pid);
+        let parent = process.parent().map_or_else(
+            || UNKNOWN_VALUE.to_owned(),
+            |parent_pid| {
+                let parent_name = system.process(parent_pid).and_then(data::extract_name);
+                match parent_name {
+                    Some(name) => format!("{parent_pid} ({name})"),
+                    None => parent_pid.to_string(),
+                }
+            },
+        );
+
+        let mut general = vec![
+            (
+                "Name",
+                data::extract_name(process)
+                    .unwrap_or(UNKNOWN_VALUE)
+                    .to_owned(),
+            ),
+            ("PID", pid.to_string()),
+            ("Parent", parent),
+            (
+                "User",
+                data::extract_user(process, &users)
+                    .unwrap_or(UNKNOWN_VALUE)
+                    .to_owned(),
+            ),
+            ("Status", format_status(data::extract_status(process))),
+            ("Command Line", data::extract_command(process)),
+            (
+                "Executable",
+                data::extract_path(process)
+                    .unwrap_or(UNKNOWN_VALUE)
+                    .to_owned(),
+            ),
+            ("Working Directory", format_path(process.cwd())),
+            ("Root", format_path(process.root())),
+            ("Start Time", format_timestamp(process.start_time())),
+            ("Run Time", format_duration(process.run_time())),
+            ("Session", format_optional(process.session_id())),
+            (
+                "Process Group",
+                format_optional(stat.as_ref().map(|stat| stat.process_group)),
+            ),
+            (
+                "TTY",
+                format_optional(stat.as_ref().and_then(|stat| data::tty_name(stat.tty_nr))),
+            ),
+            ("Exited Children", exited_children(system, pid)),
+        ];
+        general.extend(memory_rows(process));
+
+        let memory_maps = data::read_memory_maps(pid)
+            .into_iter()
+            .map(|map| {
+                format!(
+                    "{:<33} {} {} {} {:>10} {}",
+                    map.address, map.permissions, map.offset, map.device, map.inode, map.path
+                )
+            })
+            .collect();
+
+        let cpu_count = system.cpus().len().max(1) as f32;
+        let mut task_ids: Vec<sysinfo::Pid> = process
+            .tasks()
+            .map(|tasks| tasks.iter().copied().collect())
+            .unwrap_or_default();
+        if !task_ids.contains(&process.pid()) {
+            task_ids.push(process.pid());
+        }
+        task_ids.sort_unstable();
+        let threads = task_ids
+            .into_iter()
+            .filter_map(|tid| system.process(tid))
+            .map(|thread| {
+                [
+                    thread.pid().to_string(),
+                    data::extract_name(thread)
+                        .unwrap_or(UNKNOWN_VALUE)
+                        .to_owned(),
+                    format_status(data::extract_status(thread)),
+                    format_cpu(thread.cpu_usage() / cpu_count),
+                ]
+            })
+            .collect();
+
+        Self {
+            general,
+            environment: process
+                .environ()
+                .iter()
+                .map(|variable| variable.to_string_lossy().into_owned())
+                .collect(),
+            limits: data::read_limits(pid),
+            memory_maps,
+            threads,
+        }
+    }
+}
+
+pub(crate) struct ProcessDetails {
+    pid: u32,
+    tab: DetailsTab,
+    snapshot: Snapshot,
+    refreshed_at: Option<Instant>,
+    exited: bool,
+    io_priority: Result<control::IoPriority, String>,
+    affinity: Result<BTreeSet<usize>, String>,
+    status: Option<String>,
+}
+
+impl ProcessDetails {
+    fn load(system: &sysinfo::System, pid: u32) -> Self {
+        Self {
+            pid,
+            tab: DetailsTab::General,
+            snapshot: Snapshot::default(),
+            refreshed_at: None,
+            exited: false,
+            io_priority: control::io_priority(pid).map_err(|error| error.to_string()),
+            affinity: control::cpu_affinity(pid, system.cpus().len())
+                .map_err(|error| error.to_string()),
+            status: None,
+        }
+    }
+
+    pub(crate) fn refresh_due_pid(&self) -> Option<u32> {
+        let is_due = self
+            .refreshed_at
+            .is_none_or(|refreshed_at| refreshed_at.elapsed() >= DETAILS_REFRESH_INTERVAL);
+        (is_due && !self.exited).then_some(self.pid)
+    }
+
+    fn refresh(&mut self, system: &sysinfo::System) {
+        match system.process(sysinfo::Pid::from_u32(self.pid)) {
+            Some(process) => self.snapshot = Snapshot::capture(system, process),
+            None => self.exited = true,
+        }
+        self.refreshed_at = Some(Instant::now());
+    }
+}
+
+pub(super) fn open_details(system: &sysinfo::System, pid: u32, user_input: &mut UserInput) {
+    user_input.set_process_details(ProcessDetails::load(system, pid));
+}
+
+pub fn update_details(app: &app::App, ctx: &egui::Context) {
+    let (system, user_input) = (app.system(), app.user_input());
+    let refresh_pid = user_input
+        .read()
+        .ok()
+        .and_then(|user_input| user_input.process_details_refresh_pid());
+    if let Some(pid) = refresh_pid
+        && let Ok(mut system) = system.write()
+    {
+        system.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::Some(&[sysinfo::Pid::from_u32(pid)]),
+            false,
+            sysinfo::ProcessRefreshKind::nothing()
+                .with_cmd(sysinfo::UpdateKind::Always)
+                .with_cwd(sysinfo::UpdateKind::Always)
+                .with_root(sysinfo::UpdateKind::Always)
+                .with_environ(sysinfo::UpdateKind::Always),
+        );
+    }
+
+    let (Ok(system), Ok(mut user_input)) = (system.read(), user_input.write()) else {
+        return;
+    };
+    let Some(mut details) = user_input.take_process_details() else {
+        return;
+    };
+    if refresh_pid.is_some() {
+        details.refresh(&system);
+    }
+
+    let title = details.snapshot.general.first().map_or_else(
+        || format!("Process {}", details.pid),
+        |(_, name)| format!("{name} ({})", details.pid),
+    );
+
+    let mut open = true;
+    egui::Window::new(title)
+        .id(egui::Id::new(DETAILS_WINDOW_ID))
+        .open(&mut open)
+        .default_size(DETAILS_WINDOW_SIZE)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for tab in DetailsTab::ALL {
+                    ui.selectable_value(&mut details.tab, tab, tab.label());
+                }
+            });
+            ui.separator();
+
+            if details.exited {
+                ui.colored_label(ui.visuals().warn_fg_color, EXITED_TEXT);
+            }
+
+            match details.tab {
+                DetailsTab::General => general_tab(&details.snapshot, ui),
+                DetailsTab::Environment => lines_tab(&details.snapshot.environment, ui),
+                DetailsTab::Limits => limits_tab(&details.snapshot.limits, ui),
+                DetailsTab::MemoryMaps => lines_tab(&details.snapshot.memory_maps, ui),
+                DetailsTab::Threads => threads_tab(&details.snapshot.threads, ui),
+                DetailsTab::Scheduling => scheduling_tab(&mut details, system.cpus().len(), ui),
+            }
+        });
+
+    if open {
+        user_input.set_process_details(details);
+        ctx.request_repaint_after(DETAILS_REFRESH_INTERVAL);
+    }
+}
+
+fn general_tab(snapshot: &Snapshot, ui: &mut egui::Ui) {
+    egui::ScrollArea::both().show(ui, |ui| {
+        egui::Grid::new("details_general")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (label, value) in &snapshot.general {
+                    ui.strong(*label);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn lines_tab(lines: &[String], ui: &mut egui::Ui) {
+    egui::ScrollArea::both().show_rows(ui, DETAILS_ROW_HEIGHT, lines.len(), |ui, range| {
+        for line in lines.get(range).unwrap_or_default() {
+            ui.label(egui::RichText::new(line).monospace());
+        }
+    });
+}
+
+fn limits_tab(limits: &[data::ProcessLimit], ui: &mut egui::Ui) {
+    egui::ScrollArea::both().show(ui, |ui| {
+        egui::Grid::new("details_limits")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for heading in ["Limit", "Soft Limit", "Hard Limit", "Units"] {
+                    ui.strong(heading);
+                }
+                ui.end_row();
+                for limit in limits {
+                    ui.label(&limit.name);
+                    ui.label(&limit.soft);
+                    ui.label(&limit.hard);
+                    ui.label(&limit.units);
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn threads_tab(threads: &[[String; 4]], ui: &mut egui::Ui) {
+    egui::ScrollArea::both().show(ui, |ui| {
+        egui::Grid::new("details_threads")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for heading in ["TID", "Name", "Status", "CPU"] {
+                    ui.strong(heading);
+                }
+                ui.end_row();
+                for thread in threads {
+                    for value in thread {
+                        ui.label(value);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn scheduling_tab(details: &mut ProcessDetails, cpu_count: usize, ui: &mut egui::Ui) {
+    io_priority_section(details, ui);
+    ui.separator();
+    affinity_section(details, cpu_count, ui);
+
+    if let Some(status) = &details.status {
+        ui.separator();
+        ui.label(status);
+    }
+}
+
+fn memory_rows(process: &sysinfo::Process) -> [(&'static str, String); 6] {
+    let breakdown = data::read_memory_breakdown(process.pid().as_u32());
+    [
+        ("Resident Memory", format_bytes(process.memory())),
+        ("Virtual Memory", format_bytes(process.virtual_memory())),
+        (
+            "Shared Memory",
+            format_breakdown(breakdown, |breakdown| breakdown.shared),
+        ),
+        (
+            "Private Memory (USS)",
+            format_breakdown(breakdown, |breakdown| breakdown.private),
+        ),
+        (
+            "Proportional Memory (PSS)",
+            format_breakdown(breakdown, |breakdown| breakdown.proportional),
+        ),
+        (
+            "Swap",
+            format_breakdown(breakdown, |breakdown| breakdown.swap),
+        ),
+    ]
+}
+
+fn format_path(path: Option<&std::path::Path>) -> String {
+    path.map_or_else(
+        || UNKNOWN_VALUE.to_owned(),
+        |path| path.display().to_string(),
+    )
+}
+
+fn exited_children(system: &sysinfo::System, pid: u32) -> String {
+    let mut children: Vec<&sysinfo::Process> = system
+        .processes()
+        .values()
+        .filter(|process| {
+            process.parent() == Some(sysinfo::Pid::from_u32(pid))
+                && process.thread_kind().is_none()
+                && data::extract_status(process) == sysinfo::ProcessStatus::Zombie
+        })
+        .collect();
+    if children.is_empty() {
+        return "None".to_owned();
+    }
+
+    children.sort_unstable_by_key(|process| process.pid());
+    children
+        .into_iter()
+        .map(|child| {
+            let child_pid = child.pid().as_u32();
+            let exit_code = data::read_proc_stat(child_pid).and_then(|stat| stat.exit_code);
+            format!(
+                "{child_pid} ({}): {}",
+                data::extract_name(child).unwrap_or(UNKNOWN_VALUE),
+                format_exit_code(exit_code)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_exit_code(exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(code) if code & 0x7f == 0 => format!("exited with code {}", (code >> 8) & 0xff),
+        Some(code) => format!("killed by signal {}", code & 0x7f),
+        None => "exited".to_owned(),
+    }
+}
+
+fn io_priority_section(details: &mut ProcessDetails, ui: &mut egui::Ui) {
+    ui.heading(IO_PRIORITY_HEADING);
+
+    let priority = match &mut details.io_priority {
+        Ok(priority) => priority,
+        Err(error) => {
+            ui.colored_label(ui.visuals().error_fg_color, error.as_str());
+            return;
+        }
+    };
+
+    ui.horizontal(|ui| {
+        ui.label(IO_CLASS_TEXT);
+        egui::ComboBox::from_id_salt("io_class")
+            .selected_text(priority.class.label())
+            .show_ui(ui, |ui| {
+                for class in control::IoClass::ALL {
+                    ui.selectable_value(&mut priority.class, class, class.label());
+                }
+            });
+
+        ui.label(IO_LEVEL_TEXT);
+        ui.add_enabled(
+            priority.class.has_level(),
+            egui::DragValue::new(&mut priority.level).range(control::IO_LEVEL_RANGE),
+        )
+        .on_hover_text("0 is the highest priority, 7 the lowest");
+    });
+
+    if ui.button(APPLY_TEXT).clicked() {
+        let priority = *priority;
+        details.status = Some(match control::set_io_priority(details.pid, priority) {
+            Ok(()) => format!("Set I/O priority to {}", format_io_priority(priority)),
+            Err(error) => format!("Setting I/O priority failed: {error}"),
+        });
+    }
+}
+
+fn format_io_priority(priority: control::IoPriority) -> String {
+    if priority.class.has_level() {
+        format!("{} {}", priority.class.label(), priority.level)
+    } else {
+        priority.class.label().to_owned()
+    }
+}
+
+fn affinity_section(details: &mut ProcessDetails, cpu_count: usize, ui: &mut egui::Ui) {
+    ui.heading(AFFINITY_HEADING);
+
+    let affinity = match &mut details.affinity {
+        Ok(affinity) => affinity,
+        Err(error) => {
+            ui.colored_label(ui.visuals().error_fg_color, error.as_str());
+            return;
+        }
+    };
+
+    ui.horizontal(|ui| {
+        if ui.button(ALL_CPUS_TEXT).clicked() {
+            affinity.extend(0..cpu_count);
+        }
+        if ui.button(NO_CPUS_TEXT).clicked() {
+            affinity.clear();
+        }
+    });
+
+    egui::Grid::new("cpu_affinity").show(ui, |ui| {
+        for cpu in 0..cpu_count {
+            let mut allowed = affinity.contains(&cpu);
+            if ui.checkbox(&mut allowed, format!("CPU {cpu}")).changed() {
+                if allowed {
+                    affinity.insert(cpu);
+                } else {
+                    affinity.remove(&cpu);
+                }
+            }
+            if (cpu + 1) % CPUS_PER_ROW == 0 {
+                ui.end_row();
+            }
+        }
+    });
+
+    if ui.button(APPLY_TEXT).clicked() {
+        details.status = Some(match control::set_cpu_affinity(details.pid, affinity) {
+            Ok(()) => format!("Set CPU affinity to {} CPUs", affinity.len()),
+            Err(error) => format!("Setting CPU affinity failed: {error}"),
+        });
+    }
+}
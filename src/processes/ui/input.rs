@@ -1,25 +1,112 @@
 use crate::processes;
+use crate::processes::ui::{confirmation, details, presets};
+use std::collections::{BTreeSet, HashSet};
+use std::time::Duration;
+
+const APP_ID: &str = "process_manager";
+const PRESETS_FILE_NAME: &str = "process_manager_presets.ron";
+const DEFAULT_GRACE_PERIOD_SECS: u64 = 5;
+const DEFAULT_QUICK_SIGNALS: [sysinfo::Signal; 2] = [sysinfo::Signal::Term, sysinfo::Signal::Kill];
 
 #[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
 pub struct UserInput {
     #[serde(skip)]
-    selected_pid: Option<u32>,
+    selected_pids: BTreeSet<u32>,
+    #[serde(skip)]
+    selection_anchor: Option<u32>,
+    #[serde(skip)]
+    visible_pids: Vec<u32>,
 
     show_thread_processes: bool,
     hierarchical_view: bool,
+    rank_subtrees_by_usage: bool,
+    show_subtree_totals: bool,
+    collapsed_processes: HashSet<processes::ProcessKey>,
+    collapse_depth: usize,
+    #[serde(skip)]
+    pending_collapse_depth: Option<usize>,
     process_filter: String,
+    filter_options: processes::FilterOptions,
+    show_column_filters: bool,
+    show_command_in_name: bool,
+    optional_columns: BTreeSet<processes::SortCategory>,
+    column_filters: processes::ColumnFilters,
+    include_filter_descendants: bool,
     sort_method: processes::SortMethod,
+    presets: Vec<presets::FilterPreset>,
+    active_preset: Option<String>,
+    #[serde(skip)]
+    preset_name: String,
+    presets_path: String,
+    #[serde(skip)]
+    preset_message: Option<String>,
+    quick_signals: Vec<String>,
+    #[serde(skip)]
+    action_message: Option<String>,
+    #[serde(skip)]
+    action_details: Vec<String>,
+    suspend_subtree: bool,
+    #[serde(skip)]
+    renice_value: i32,
+    tree_signal_order: processes::TreeSignalOrder,
+    escalate_tree_termination: bool,
+    grace_period_secs: u64,
+    confirm_escalation: bool,
+    confirm_destructive_actions: bool,
+    protected_processes: processes::ProtectedProcesses,
+    #[serde(skip)]
+    process_details: Option<details::ProcessDetails>,
+    #[serde(skip)]
+    pending_confirmation: Option<confirmation::PendingConfirmation>,
+    #[serde(skip)]
+    terminations: Vec<processes::Termination>,
     continue_refreshing: bool,
 }
 
 impl Default for UserInput {
     fn default() -> Self {
         Self {
-            selected_pid: None,
+            selected_pids: BTreeSet::new(),
+            selection_anchor: None,
+            visible_pids: Vec::new(),
             show_thread_processes: false,
             hierarchical_view: true,
+            rank_subtrees_by_usage: false,
+            show_subtree_totals: false,
+            collapsed_processes: HashSet::new(),
+            collapse_depth: 1,
+            pending_collapse_depth: None,
             process_filter: String::new(),
+            filter_options: processes::FilterOptions::default(),
+            show_column_filters: false,
+            show_command_in_name: false,
+            optional_columns: BTreeSet::new(),
+            column_filters: processes::ColumnFilters::default(),
+            include_filter_descendants: false,
             sort_method: Default::default(),
+            presets: Vec::new(),
+            active_preset: None,
+            preset_name: String::new(),
+            presets_path: default_presets_path(),
+            preset_message: None,
+            quick_signals: DEFAULT_QUICK_SIGNALS
+                .into_iter()
+                .map(|signal| processes::signal_name(signal).to_owned())
+                .collect(),
+            action_message: None,
+            action_details: Vec::new(),
+            suspend_subtree: false,
+            renice_value: 0,
+            tree_signal_order: processes::TreeSignalOrder::default(),
+            escalate_tree_termination: true,
+            grace_period_secs: DEFAULT_GRACE_PERIOD_SECS,
+            confirm_escalation: false,
+            confirm_destructive_actions: true,
+            protected_processes: processes::ProtectedProcesses::default(),
+            process_details: None,
+            pending_confirmation: None,
+            terminations: Vec::new(),
             continue_refreshing: true,
         }
     }
@@ -27,11 +114,62 @@ impl Default for UserInput {
 
 impl UserInput {
     pub(crate) fn selected_pid(&self) -> Option<u32> {
-        self.selected_pid
+        match self.selected_pids.len() {
+            1 => self.selected_pids.first().copied(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn selected_pids(&self) -> Vec<u32> {
+        self.selected_pids.iter().copied().collect()
+    }
+
+    pub(crate) fn is_selected(&self, pid: u32) -> bool {
+        self.selected_pids.contains(&pid)
+    }
+
+    pub(crate) fn select(&mut self, pid: u32, modifiers: egui::Modifiers) {
+        if modifiers.shift
+            && let Some(anchor) = self.selection_anchor
+        {
+            let anchor_index = self
+                .visible_pids
+                .iter()
+                .position(|&visible| visible == anchor);
+            let pid_index = self.visible_pids.iter().position(|&visible| visible == pid);
+            if let (Some(anchor_index), Some(pid_index)) = (anchor_index, pid_index) {
+                let range = anchor_index.min(pid_index)..=anchor_index.max(pid_index);
+                if !modifiers.command {
+                    self.selected_pids.clear();
+                }
+                self.selected_pids
+                    .extend(self.visible_pids.get(range).unwrap_or_default());
+                return;
+            }
+        }
+
+        if modifiers.command {
+            if !self.selected_pids.remove(&pid) {
+                self.selected_pids.insert(pid);
+            }
+        } else {
+            self.selected_pids.clear();
+            self.selected_pids.insert(pid);
+        }
+        self.selection_anchor = Some(pid);
+    }
+
+    pub(crate) fn set_visible_pids(&mut self, visible_pids: Vec<u32>) {
+        self.visible_pids = visible_pids;
     }
 
-    pub(crate) fn set_selected_pid(&mut self, pid: Option<u32>) {
-        self.selected_pid = pid;
+    pub(crate) fn select_all_visible(&mut self) {
+        self.selected_pids.extend(self.visible_pids.iter().copied());
+    }
+
+    pub(crate) fn clear_selection(&mut self) {
+        self.selected_pids.clear();
+        self.selection_anchor = None;
     }
 
     pub(crate) fn process_filter(&self) -> &str {
@@ -46,6 +184,67 @@ impl UserInput {
         &mut self.process_filter
     }
 
+    pub(crate) fn filter_options(&self) -> processes::FilterOptions {
+        self.filter_options
+    }
+
+    pub(crate) fn filter_options_mut(&mut self) -> &mut processes::FilterOptions {
+        &mut self.filter_options
+    }
+
+    pub(crate) fn show_column_filters(&self) -> bool {
+        self.show_column_filters
+    }
+
+    pub(crate) fn show_column_filters_mut(&mut self) -> &mut bool {
+        &mut self.show_column_filters
+    }
+
+    pub(crate) fn show_command_in_name(&self) -> bool {
+        self.show_command_in_name
+    }
+
+    pub(crate) fn show_command_in_name_mut(&mut self) -> &mut bool {
+        &mut self.show_command_in_name
+    }
+
+    pub(crate) fn shows_optional_column(&self, category: processes::SortCategory) -> bool {
+        self.optional_columns.contains(&category)
+    }
+
+    pub(crate) fn shows_memory_breakdown(&self) -> bool {
+        processes::SortCategory::MEMORY_BREAKDOWN
+            .into_iter()
+            .any(|category| self.shows_optional_column(category))
+    }
+
+    pub(crate) fn set_optional_column(&mut self, category: processes::SortCategory, shown: bool) {
+        if shown {
+            self.optional_columns.insert(category);
+        } else {
+            self.optional_columns.remove(&category);
+            if let Some(text) = self.column_filters.text_mut(category) {
+                text.clear();
+            }
+        }
+    }
+
+    pub(crate) fn column_filters(&self) -> &processes::ColumnFilters {
+        &self.column_filters
+    }
+
+    pub(crate) fn column_filters_mut(&mut self) -> &mut processes::ColumnFilters {
+        &mut self.column_filters
+    }
+
+    pub(crate) fn include_filter_descendants(&self) -> bool {
+        self.include_filter_descendants
+    }
+
+    pub(crate) fn include_filter_descendants_mut(&mut self) -> &mut bool {
+        &mut self.include_filter_descendants
+    }
+
     pub(crate) fn hierarchical_view(&self) -> bool {
         self.hierarchical_view
     }
@@ -54,6 +253,59 @@ impl UserInput {
         &mut self.hierarchical_view
     }
 
+    pub(crate) fn rank_subtrees_by_usage(&self) -> bool {
+        self.rank_subtrees_by_usage
+    }
+
+    pub(crate) fn rank_subtrees_by_usage_mut(&mut self) -> &mut bool {
+        &mut self.rank_subtrees_by_usage
+    }
+
+    pub(crate) fn show_subtree_totals(&self) -> bool {
+        self.show_subtree_totals
+    }
+
+    pub(crate) fn show_subtree_totals_mut(&mut self) -> &mut bool {
+        &mut self.show_subtree_totals
+    }
+
+    pub(crate) fn is_collapsed(&self, key: processes::ProcessKey) -> bool {
+        self.collapsed_processes.contains(&key)
+    }
+
+    pub(crate) fn set_collapsed(&mut self, key: processes::ProcessKey, collapsed: bool) {
+        if collapsed {
+            self.collapsed_processes.insert(key);
+        } else {
+            self.collapsed_processes.remove(&key);
+        }
+    }
+
+    pub(crate) fn expand_all(&mut self) {
+        self.collapsed_processes.clear();
+    }
+
+    pub(crate) fn retain_running_collapsed(&mut self, system: &sysinfo::System) {
+        self.collapsed_processes
+            .retain(|key| key.is_running(system));
+    }
+
+    pub(crate) fn collapse_depth_mut(&mut self) -> &mut usize {
+        &mut self.collapse_depth
+    }
+
+    pub(crate) fn collapse_to_depth(&mut self) {
+        self.pending_collapse_depth = Some(self.collapse_depth);
+    }
+
+    pub(crate) fn collapse_all(&mut self) {
+        self.pending_collapse_depth = Some(0);
+    }
+
+    pub(crate) fn take_pending_collapse_depth(&mut self) -> Option<usize> {
+        self.pending_collapse_depth.take()
+    }
+
     pub(crate) fn show_thread_processes(&self) -> bool {
         self.show_thread_processes
     }
@@ -77,4 +329,350 @@ impl UserInput {
     pub(crate) fn set_continue_refreshing(&mut self, continue_refreshing: bool) {
         self.continue_refreshing = continue_refreshing;
     }
+
+    pub(crate) fn quick_signals(&self) -> Vec<sysinfo::Signal> {
+        self.quick_signals
+            .iter()
+            .filter_map(|name| processes::signal_from_name(name))
+            .collect()
+    }
+
+    pub(crate) fn is_quick_signal(&self, signal: sysinfo::Signal) -> bool {
+        let name = processes::signal_name(signal);
+        self.quick_signals
+            .iter()
+            .any(|quick_signal| quick_signal == name)
+    }
+
+    pub(crate) fn set_quick_signal(&mut self, signal: sysinfo::Signal, quick: bool) {
+        let name = processes::signal_name(signal);
+        self.quick_signals
+            .retain(|quick_signal| quick_signal != name);
+        if quick {
+            self.quick_signals.push(name.to_owned());
+        }
+    }
+
+    pub(crate) fn action_message(&self) -> Option<&str> {
+        self.action_message.as_deref()
+    }
+
+    pub(crate) fn set_action_message(&mut self, message: String) {
+        self.action_message = Some(message);
+        self.action_details.clear();
+    }
+
+    pub(crate) fn action_details(&self) -> &[String] {
+        &self.action_details
+    }
+
+    pub(crate) fn set_action_report(&mut self, report: &processes::SignalReport) {
+        self.set_action_details(report.to_string(), report.details());
+    }
+
+    pub(crate) fn set_action_details(&mut self, message: String, details: Vec<String>) {
+        self.action_message = Some(message);
+        self.action_details = details;
+    }
+
+    pub(crate) fn suspend_subtree(&self) -> bool {
+        self.suspend_subtree
+    }
+
+    pub(crate) fn suspend_subtree_mut(&mut self) -> &mut bool {
+        &mut self.suspend_subtree
+    }
+
+    pub(crate) fn renice_value(&self) -> i32 {
+        self.renice_value
+    }
+
+    pub(crate) fn renice_value_mut(&mut self) -> &mut i32 {
+        &mut self.renice_value
+    }
+
+    pub(crate) fn tree_signal_order_mut(&mut self) -> &mut processes::TreeSignalOrder {
+        &mut self.tree_signal_order
+    }
+
+    pub(crate) fn escalate_tree_termination_mut(&mut self) -> &mut bool {
+        &mut self.escalate_tree_termination
+    }
+
+    pub(crate) fn grace_period_secs_mut(&mut self) -> &mut u64 {
+        &mut self.grace_period_secs
+    }
+
+    pub(crate) fn confirm_escalation_mut(&mut self) -> &mut bool {
+        &mut self.confirm_escalation
+    }
+
+    pub(crate) fn confirm_destructive_actions(&self) -> bool {
+        self.confirm_destructive_actions
+    }
+
+    pub(crate) fn confirm_destructive_actions_mut(&mut self) -> &mut bool {
+        &mut self.confirm_destructive_actions
+    }
+
+    pub(crate) fn protected_processes(&self) -> &processes::ProtectedProcesses {
+        &self.protected_processes
+    }
+
+    pub(crate) fn protected_processes_mut(&mut self) -> &mut processes::ProtectedProcesses {
+        &mut self.protected_processes
+    }
+
+    pub(crate) fn set_process_details(&mut self, process_details: details::ProcessDetails) {
+        self.process_details = Some(process_details);
+    }
+
+    pub(crate) fn process_details_refresh_pid(&self) -> Option<u32> {
+        self.process_details
+            .as_ref()
+            .and_then(details::ProcessDetails::refresh_due_pid)
+    }
+
+    pub(crate) fn take_process_details(&mut self) -> Option<details::ProcessDetails> {
+        self.process_details.take()
+    }
+
+    pub(crate) fn set_pending_confirmation(
+        &mut self,
+        pending_confirmation: confirmation::PendingConfirmation,
+    ) {
+        self.pending_confirmation = Some(pending_confirmation);
+    }
+
+    pub(crate) fn take_pending_confirmation(
+        &mut self,
+    ) -> Option<confirmation::PendingConfirmation> {
+        self.pending_confirmation.take()
+    }
+
+    fn escalation(&self) -> processes::Escalation {
+        if self.confirm_escalation {
+            processes::Escalation::Confirm
+        } else {
+            processes::Escalation::Automatic
+        }
+    }
+
+    pub(crate) fn start_tree_termination(
+        &mut self,
+        system: &sysinfo::System,
+        root_pid: u32,
+        signal: sysinfo::Signal,
+    ) -> processes::SignalReport {
+        let escalation = if self.escalate_tree_termination {
+            self.escalation()
+        } else {
+            processes::Escalation::Never
+        };
+        let (termination, report) = processes::Termination::start_tree(
+            system,
+            root_pid,
+            self.tree_signal_order,
+            signal,
+            Duration::from_secs(self.grace_period_secs),
+            escalation,
+        );
+        self.terminations.push(termination);
+        report
+    }
+
+    pub(crate) fn start_stop(
+        &mut self,
+        system: &sysinfo::System,
+        pid: u32,
+    ) -> processes::SignalReport {
+        let (termination, report) = processes::Termination::start_stop(
+            system,
+            pid,
+            Duration::from_secs(self.grace_period_secs),
+            self.escalation(),
+        );
+        self.terminations.push(termination);
+        report
+    }
+
+    pub(crate) fn terminations(&self) -> &[processes::Termination] {
+        &self.terminations
+    }
+
+    pub(crate) fn kill_termination_survivors(&mut self, index: usize, system: &sysinfo::System) {
+        if let Some(termination) = self.terminations.get_mut(index) {
+            self.action_message = Some(termination.kill_survivors(system));
+        }
+    }
+
+    pub(crate) fn dismiss_termination(&mut self, index: usize) {
+        if index < self.terminations.len() {
+            self.terminations.remove(index);
+        }
+    }
+
+    pub(crate) fn watched_pids(&self) -> Vec<sysinfo::Pid> {
+        self.terminations
+            .iter()
+            .flat_map(processes::Termination::watched_pids)
+            .collect()
+    }
+
+    pub(crate) fn check_terminations(&mut self, system: &sysinfo::System) {
+        if let Some(message) = processes::check_terminations(system, &mut self.terminations) {
+            self.action_message = Some(message);
+        }
+    }
+
+    pub(crate) fn preset_names(&self) -> Vec<String> {
+        self.presets
+            .iter()
+            .map(|preset| preset.name.clone())
+            .collect()
+    }
+
+    pub(crate) fn active_preset(&self) -> Option<&str> {
+        self.active_preset.as_deref()
+    }
+
+    pub(crate) fn apply_preset(&mut self, name: &str) {
+        let Some(preset) = self.presets.iter().find(|preset| preset.name == name) else {
+            return;
+        };
+        let preset = preset.clone();
+
+        self.process_filter = preset.process_filter;
+        self.filter_options = preset.filter_options;
+        self.show_column_filters = preset.show_column_filters;
+        self.column_filters = preset.column_filters;
+        self.include_filter_descendants = preset.include_filter_descendants;
+        self.sort_method = preset.sort_method;
+        self.show_thread_processes = preset.show_thread_processes;
+        self.hierarchical_view = preset.hierarchical_view;
+        self.active_preset = Some(preset.name);
+    }
+
+    pub(crate) fn save_new_preset(&mut self, name: &str) {
+        if self.has_preset(name) {
+            self.preset_message = Some(format!("A preset named {name:?} already exists"));
+            return;
+        }
+        self.save_preset(name);
+    }
+
+    pub(crate) fn save_preset(&mut self, name: &str) {
+        let preset = presets::FilterPreset {
+            name: name.to_owned(),
+            process_filter: self.process_filter.clone(),
+            filter_options: self.filter_options,
+            show_column_filters: self.show_column_filters,
+            column_filters: self.column_filters.clone(),
+            include_filter_descendants: self.include_filter_descendants,
+            sort_method: self.sort_method.clone(),
+            show_thread_processes: self.show_thread_processes,
+            hierarchical_view: self.hierarchical_view,
+        };
+        self.insert_preset(preset);
+        self.active_preset = Some(name.to_owned());
+    }
+
+    pub(crate) fn rename_active_preset(&mut self, new_name: &str) {
+        let Some(active_preset) = self.active_preset.take() else {
+            return;
+        };
+        if active_preset == new_name {
+            self.active_preset = Some(active_preset);
+            return;
+        }
+        if self.has_preset(new_name) {
+            self.preset_message = Some(format!("A preset named {new_name:?} already exists"));
+            self.active_preset = Some(active_preset);
+            return;
+        }
+        if let Some(preset) = self
+            .presets
+            .iter_mut()
+            .find(|preset| preset.name == active_preset)
+        {
+            new_name.clone_into(&mut preset.name);
+        }
+        self.active_preset = Some(new_name.to_owned());
+    }
+
+    pub(crate) fn delete_active_preset(&mut self) {
+        let Some(active_preset) = self.active_preset.take() else {
+            return;
+        };
+        self.presets.retain(|preset| preset.name != active_preset);
+    }
+
+    pub(crate) fn preset_name_mut(&mut self) -> &mut String {
+        &mut self.preset_name
+    }
+
+    pub(crate) fn presets_path_mut(&mut self) -> &mut String {
+        &mut self.presets_path
+    }
+
+    pub(crate) fn preset_message(&self) -> Option<&str> {
+        self.preset_message.as_deref()
+    }
+
+    pub(crate) fn export_presets(&mut self) {
+        self.preset_message = Some(
+            match presets::export_presets(&self.presets_path, &self.presets) {
+                Ok(()) => format!("Exported {} presets", self.presets.len()),
+                Err(error) => format!("Export failed: {error}"),
+            },
+        );
+    }
+
+    pub(crate) fn import_presets(&mut self) {
+        self.preset_message = Some(match presets::import_presets(&self.presets_path) {
+            Ok(imported) => {
+                let count = imported.len();
+                let mut skipped = Vec::new();
+                for preset in imported {
+                    if self.has_preset(&preset.name) {
+                        skipped.push(format!("{:?}", preset.name));
+                    } else {
+                        self.presets.push(preset);
+                    }
+                }
+                if skipped.is_empty() {
+                    format!("Imported {count} presets")
+                } else {
+                    format!(
+                        "Imported {} of {count} presets; skipped existing names: {}",
+                        count - skipped.len(),
+                        skipped.join(", ")
+                    )
+                }
+            }
+            Err(error) => format!("Import failed: {error}"),
+        });
+    }
+
+    fn has_preset(&self, name: &str) -> bool {
+        self.presets.iter().any(|preset| preset.name == name)
+    }
+
+    fn insert_preset(&mut self, preset: presets::FilterPreset) {
+        match self
+            .presets
+            .iter_mut()
+            .find(|existing| existing.name == preset.name)
+        {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+}
+
+fn default_presets_path() -> String {
+    eframe::storage_dir(APP_ID).map_or_else(
+        || PRESETS_FILE_NAME.to_owned(),
+        |directory| directory.join(PRESETS_FILE_NAME).display().to_string(),
+    )
 }
@@ -1,30 +1,90 @@
+mod actions;
+mod confirmation;
+mod control_bar;
+mod details;
 mod input;
+mod presets;
 
 use crate::{app, processes};
+use chrono::Datelike as _;
 use processes::data;
+use std::collections::BTreeSet;
 use std::ops::RangeInclusive;
 
 pub use input::UserInput;
 
 const HEADER_TEXT_SIZE: f32 = 12.0;
 const HEADER_HEIGHT: f32 = 25.0;
+const COLUMN_FILTER_HEIGHT: f32 = 22.0;
 const ROW_HEIGHT: f32 = 18.0;
 const CONTROL_PANEL_HEIGHT: f32 = 30.0;
 const LARGE_COLUMN_WIDTH: f32 = 250.0;
 const SMALL_COLUMNS_WIDTH: f32 = 65.0;
 const COLUMN_WIDTH_RANGE: RangeInclusive<f32> = 65.0..=500.0;
 
-const CLIPBOARD_SYMBOL: &str = "📋";
 const PLAY_SYMBOL: &str = "▶";
 const PAUSE_SYMBOL: &str = "⏸";
 const REFRESH_SYMBOL: &str = "⟳";
 const X_SYMBOL: &str = "X";
-
-const CHILD_POINT_SYMBOL: &str = "> ";
-const CHILD_SPACE_SYMBOL: &str = "     ";
-
-const BLANK_PROCESS_PATH: &str = "";
-const BLANK_PROCESS_NAME: &str = "";
+const CASE_INSENSITIVE_SYMBOL: &str = "Aa";
+const WHOLE_WORD_SYMBOL: &str = "W";
+const REGEX_SYMBOL: &str = ".*";
+const FILTER_MENU_SYMBOL: &str = "⏷";
+const SELECT_ALL_TEXT: &str = "Select All";
+
+const TREE_BRANCH_SYMBOL: &str = "├─";
+const TREE_LAST_BRANCH_SYMBOL: &str = "└─";
+const TREE_LINE_SYMBOL: &str = "│ ";
+const TREE_SPACE_SYMBOL: &str = "  ";
+const EXPANDED_SYMBOL: &str = "⏷";
+const COLLAPSED_SYMBOL: &str = "⏵";
+const SUBTREE_TOTAL_SYMBOL: &str = "Σ ";
+const PARTIAL_TOTAL_SYMBOL: &str = "≥";
+const TREE_TOGGLE_WIDTH: f32 = 16.0;
+
+const HEADER_COLUMNS: [(&str, data::SortCategory); 23] = [
+    ("Name", data::SortCategory::Name),
+    ("ID", data::SortCategory::Id),
+    ("PPID", data::SortCategory::ParentId),
+    ("User", data::SortCategory::User),
+    ("Memory", data::SortCategory::Memory),
+    ("VIRT", data::SortCategory::VirtualMemory),
+    ("Shared", data::SortCategory::SharedMemory),
+    ("USS", data::SortCategory::PrivateMemory),
+    ("PSS", data::SortCategory::ProportionalMemory),
+    ("Swap", data::SortCategory::Swap),
+    ("CPU", data::SortCategory::Cpu),
+    ("Nice", data::SortCategory::Nice),
+    ("Threads", data::SortCategory::Threads),
+    ("Disk Read", data::SortCategory::DiskRead),
+    ("Disk Write", data::SortCategory::DiskWrite),
+    ("Path", data::SortCategory::Path),
+    ("Status", data::SortCategory::Status),
+    ("Started", data::SortCategory::StartTime),
+    ("Run Time", data::SortCategory::RunTime),
+    ("Session", data::SortCategory::Session),
+    ("Process Group", data::SortCategory::ProcessGroup),
+    ("TTY", data::SortCategory::Tty),
+    ("Command", data::SortCategory::Command),
+];
+const OPTIONAL_COLUMNS: [data::SortCategory; 10] = [
+    data::SortCategory::ParentId,
+    data::SortCategory::VirtualMemory,
+    data::SortCategory::SharedMemory,
+    data::SortCategory::PrivateMemory,
+    data::SortCategory::ProportionalMemory,
+    data::SortCategory::Swap,
+    data::SortCategory::Threads,
+    data::SortCategory::Session,
+    data::SortCategory::ProcessGroup,
+    data::SortCategory::Tty,
+];
+
+const UNKNOWN_VALUE: &str = "-";
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const START_TIME_TODAY_FORMAT: &str = "%H:%M:%S";
+const START_TIME_THIS_YEAR_FORMAT: &str = "%b %d %H:%M";
+const START_TIME_FORMAT: &str = "%Y-%m-%d";
 
 const ASCENDING_SYMBOL: &str = "⏶";
 const DESCENDING_SYMBOL: &str = "⏷";
@@ -36,18 +96,21 @@ pub fn update(app: &app::App, ctx: &egui::Context) {
     egui::TopBottomPanel::bottom("control_bar")
         .exact_height(CONTROL_PANEL_HEIGHT)
         .show(ctx, |ui| {
-            update_control_bar(app, ctx, ui);
+            control_bar::update_control_bar(app, ctx, ui);
         });
     egui::CentralPanel::default().show(ctx, |ui| {
         update_table(app, ui);
     });
+    details::update_details(app, ctx);
+    confirmation::update_confirmation(app, ctx);
 }
 
 fn update_options_panel(app: &app::App, ui: &mut egui::Ui) {
-    let (system, user_input) = (app.system(), app.user_input());
+    let user_input = app.user_input();
     let Ok(mut user_input) = user_input.write() else {
         return;
     };
+    let mut refresh_clicked = false;
     ui.horizontal(|ui| {
         if ui.button(PLAY_SYMBOL).clicked() {
             user_input.set_continue_refreshing(true);
@@ -58,112 +121,203 @@ fn update_options_panel(app: &app::App, ui: &mut egui::Ui) {
         }
 
         if ui.button(REFRESH_SYMBOL).clicked() {
-            if let Ok(mut system) = system.write() {
-                system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
-            }
+            refresh_clicked = true;
             ui.ctx().request_repaint();
         }
 
         ui.separator();
 
-        ui.add(
-            egui::TextEdit::singleline(user_input.process_filter_mut())
-                .hint_text("Filter by name, user, or path"),
-        );
+        let filter_error =
+            data::FilterQuery::parse(user_input.process_filter(), user_input.filter_options())
+                .err();
+        ui.vertical(|ui| {
+            let mut filter_edit = egui::TextEdit::singleline(user_input.process_filter_mut())
+                .hint_text("Filter, e.g. user:root cpu>5 age<5m !name:bash");
+            if filter_error.is_some() {
+                filter_edit = filter_edit.text_color(ui.visuals().error_fg_color);
+            }
+            ui.add(filter_edit);
+            if let Some(error) = &filter_error {
+                ui.colored_label(ui.visuals().error_fg_color, error.to_string());
+            }
+        });
 
         if ui.button(X_SYMBOL).clicked() {
             user_input.clear_process_filter();
         }
 
+        if ui
+            .button(SELECT_ALL_TEXT)
+            .on_hover_text("Select all processes matching the filter")
+            .clicked()
+        {
+            user_input.select_all_visible();
+        }
+
+        let filter_options = user_input.filter_options_mut();
+        ui.toggle_value(
+            &mut filter_options.case_insensitive,
+            CASE_INSENSITIVE_SYMBOL,
+        )
+        .on_hover_text("Case-insensitive matching");
+        ui.toggle_value(&mut filter_options.whole_word, WHOLE_WORD_SYMBOL)
+            .on_hover_text("Match whole words only");
+        ui.toggle_value(&mut filter_options.regex, REGEX_SYMBOL)
+            .on_hover_text("Treat filter text as regular expressions");
+
         ui.separator();
 
         ui.checkbox(user_input.show_thread_processes_mut(), "Include Threads");
         ui.checkbox(user_input.hierarchical_view_mut(), "Hierarchical View");
-    });
-}
+        ui.checkbox(user_input.show_column_filters_mut(), "Column Filters");
+        columns_menu(&mut user_input, ui);
+        ui.checkbox(
+            user_input.show_command_in_name_mut(),
+            "Command Line in Name",
+        );
 
-fn update_control_bar(app: &app::App, ctx: &egui::Context, ui: &mut egui::Ui) {
-    let (system, user_input) = (app.system(), app.user_input());
-    let (Ok(system), Ok(user_input)) = (system.read(), user_input.read()) else {
-        return;
-    };
+        ui.separator();
 
-    let Some(selected_pid) = user_input.selected_pid() else {
-        return;
-    };
+        update_presets(&mut user_input, ui);
+    });
 
-    let Some(process) = system
-        .processes()
-        .get(&sysinfo::Pid::from_u32(selected_pid))
-    else {
-        return;
-    };
+    if user_input.hierarchical_view() {
+        update_tree_options(&mut user_input, ui);
+    }
+    let read_memory_breakdown = user_input.shows_memory_breakdown();
+    drop(user_input);
 
-    ui.horizontal_centered(|ui| {
-        if ui.button("Terminate").clicked() {
-            process.kill_with(sysinfo::Signal::Term);
+    if refresh_clicked {
+        let (system, proc_cache) = (app.system(), app.proc_cache());
+        if let (Ok(mut system), Ok(mut proc_cache)) = (system.write(), proc_cache.write()) {
+            data::refresh_processes(&mut system, &mut proc_cache, read_memory_breakdown);
         }
+    }
+}
 
-        if ui.button("Kill").clicked() {
-            process.kill_with(sysinfo::Signal::Kill);
+fn columns_menu(user_input: &mut UserInput, ui: &mut egui::Ui) {
+    ui.menu_button("Columns", |ui| {
+        for (text, category) in HEADER_COLUMNS {
+            if !OPTIONAL_COLUMNS.contains(&category) {
+                continue;
+            }
+            let mut shown = user_input.shows_optional_column(category);
+            if ui.checkbox(&mut shown, text).changed() {
+                user_input.set_optional_column(category, shown);
+            }
         }
+    });
+}
+
+fn update_presets(user_input: &mut UserInput, ui: &mut egui::Ui) {
+    let mut selected_preset = None;
+    egui::ComboBox::from_id_salt("filter_preset")
+        .selected_text(user_input.active_preset().unwrap_or("Presets"))
+        .show_ui(ui, |ui| {
+            for name in user_input.preset_names() {
+                let active = user_input.active_preset() == Some(name.as_str());
+                if ui.selectable_label(active, &name).clicked() {
+                    selected_preset = Some(name);
+                }
+            }
+        });
+    if let Some(name) = selected_preset {
+        user_input.apply_preset(&name);
+    }
+
+    if let Some(active_preset) = user_input.active_preset().map(str::to_owned)
+        && ui.button("Save").clicked()
+    {
+        user_input.save_preset(&active_preset);
+    }
+
+    ui.menu_button("Manage", |ui| {
+        ui.add(egui::TextEdit::singleline(user_input.preset_name_mut()).hint_text("Preset name"));
+        let preset_name = user_input.preset_name_mut().trim().to_owned();
+        let has_active_preset = user_input.active_preset().is_some();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!preset_name.is_empty(), egui::Button::new("Save as New"))
+                .clicked()
+            {
+                user_input.save_new_preset(&preset_name);
+            }
+            if ui
+                .add_enabled(
+                    has_active_preset && !preset_name.is_empty(),
+                    egui::Button::new("Rename"),
+                )
+                .clicked()
+            {
+                user_input.rename_active_preset(&preset_name);
+            }
+            if ui
+                .add_enabled(has_active_preset, egui::Button::new("Delete"))
+                .clicked()
+            {
+                user_input.delete_active_preset();
+            }
+        });
 
         ui.separator();
 
-        if let Some(name) = data::extract_name(process) {
-            if ui.button(CLIPBOARD_SYMBOL).clicked() {
-                ctx.copy_text(
-                    data::extract_name(process)
-                        .unwrap_or(BLANK_PROCESS_NAME)
-                        .to_owned(),
-                );
+        ui.add(egui::TextEdit::singleline(user_input.presets_path_mut()).hint_text("File path"));
+        ui.horizontal(|ui| {
+            if ui.button("Import").clicked() {
+                user_input.import_presets();
+            }
+            if ui.button("Export").clicked() {
+                user_input.export_presets();
             }
-            ui.label(name);
-            ui.separator();
+        });
+        if let Some(message) = user_input.preset_message() {
+            ui.label(message);
+        }
+    });
+}
+
+fn update_tree_options(user_input: &mut UserInput, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        if ui.button("Expand All").clicked() {
+            user_input.expand_all();
+        }
+
+        if ui.button("Collapse All").clicked() {
+            user_input.collapse_all();
         }
 
-        if ui.button(CLIPBOARD_SYMBOL).clicked() {
-            ctx.copy_text(selected_pid.to_string());
+        if ui.button("Collapse to Depth").clicked() {
+            user_input.collapse_to_depth();
         }
-        ui.label(selected_pid.to_string());
+        ui.add(egui::DragValue::new(user_input.collapse_depth_mut()).range(0..=64));
 
         ui.separator();
 
-        if let Some(path) = data::extract_path(process) {
-            if ui.button(CLIPBOARD_SYMBOL).clicked() {
-                ctx.copy_text(
-                    data::extract_path(process)
-                        .unwrap_or(BLANK_PROCESS_PATH)
-                        .to_owned(),
-                );
-            }
-            ui.label(path);
-            ui.separator();
-        }
+        ui.checkbox(
+            user_input.include_filter_descendants_mut(),
+            "Filter Includes Descendants",
+        );
+        ui.checkbox(user_input.show_subtree_totals_mut(), "Show Subtree Totals");
+        ui.checkbox(
+            user_input.rank_subtrees_by_usage_mut(),
+            "Rank Subtrees by Totals",
+        );
     });
-
-    ui.separator();
 }
 
 fn header_name_label(text: &str, ui: &mut egui::Ui) {
     ui.label(egui::RichText::new(text).font(egui::FontId::proportional(HEADER_TEXT_SIZE)));
 }
 
-fn header_sort_label(sort_direction: &data::SortDirection, ui: &mut egui::Ui) {
-    match sort_direction {
-        data::SortDirection::Ascending => {
-            ui.label(
-                egui::RichText::new(ASCENDING_SYMBOL)
-                    .font(egui::FontId::proportional(HEADER_TEXT_SIZE)),
-            );
-        }
-        data::SortDirection::Descending => {
-            ui.label(
-                egui::RichText::new(DESCENDING_SYMBOL)
-                    .font(egui::FontId::proportional(HEADER_TEXT_SIZE)),
-            );
-        }
+fn header_sort_label(sort_key: data::SortKey, position: Option<usize>, ui: &mut egui::Ui) {
+    let mut text = match sort_key.direction {
+        data::SortDirection::Ascending => ASCENDING_SYMBOL.to_owned(),
+        data::SortDirection::Descending => DESCENDING_SYMBOL.to_owned(),
+    };
+    if let Some(position) = position {
+        text.push_str(&(position + 1).to_string());
     }
+    ui.label(egui::RichText::new(text).font(egui::FontId::proportional(HEADER_TEXT_SIZE)));
 }
 
 fn response_primary_clicked(response: &egui::Response) -> bool {
@@ -172,15 +326,12 @@ fn response_primary_clicked(response: &egui::Response) -> bool {
 
 fn header_cell(
     text: &str,
-    mut header_category: Option<data::SortCategory>,
-    sorting: bool,
+    header_category: Option<data::SortCategory>,
     current_sort_method: &mut data::SortMethod,
     ui: &mut egui::Ui,
 ) {
     ui.style_mut().interaction.selectable_labels = false;
 
-    header_category = if sorting { header_category } else { None };
-
     let Some(sort_category) = header_category else {
         ui.horizontal_centered(|ui| {
             header_name_label(text, ui);
@@ -188,10 +339,11 @@ fn header_cell(
         return;
     };
 
-    if current_sort_method.category == sort_category {
+    if let Some((position, sort_key)) = current_sort_method.key_position(sort_category) {
+        let position = (current_sort_method.key_count() > 1).then_some(position);
         ui.horizontal_centered(|ui| {
             header_name_label(text, ui);
-            header_sort_label(&current_sort_method.direction, ui);
+            header_sort_label(sort_key, position, ui);
         });
     } else {
         ui.horizontal_centered(|ui| {
@@ -204,11 +356,10 @@ fn header_cell(
     }
 
     if response_primary_clicked(&ui.response()) {
-        if current_sort_method.category == sort_category {
-            current_sort_method.toggle_direction();
+        if ui.input(|i| i.modifiers.shift) {
+            current_sort_method.add_secondary(sort_category);
         } else {
-            current_sort_method.category = sort_category;
-            current_sort_method.direction = data::SortDirection::Ascending;
+            current_sort_method.select(sort_category);
         }
     }
 }
@@ -218,10 +369,193 @@ fn body_cell(text: &str, ui: &mut egui::Ui) {
     ui.label(text);
 }
 
+fn row_text_color(process_info: &data::ProcessInfo, ui: &mut egui::Ui) {
+    if process_info.filter_context {
+        ui.visuals_mut().override_text_color = Some(ui.visuals().weak_text_color());
+    } else if process_info.status == sysinfo::ProcessStatus::Stop {
+        ui.visuals_mut().override_text_color = Some(ui.visuals().warn_fg_color);
+    }
+}
+
+fn tree_guides_text(tree_guides: &[bool]) -> String {
+    let Some((&has_next_sibling, ancestors)) = tree_guides.split_last() else {
+        return String::new();
+    };
+
+    let mut text: String = ancestors
+        .iter()
+        .map(|&continues| {
+            if continues {
+                TREE_LINE_SYMBOL
+            } else {
+                TREE_SPACE_SYMBOL
+            }
+        })
+        .collect();
+    text.push_str(if has_next_sibling {
+        TREE_BRANCH_SYMBOL
+    } else {
+        TREE_LAST_BRANCH_SYMBOL
+    });
+    text
+}
+
+fn displayed_name<'a>(process_info: &'a data::ProcessInfo, user_input: &UserInput) -> &'a str {
+    if user_input.show_command_in_name() && !process_info.command.is_empty() {
+        &process_info.command
+    } else {
+        &process_info.name
+    }
+}
+
+fn name_cell(process_info: &data::ProcessInfo, user_input: &mut UserInput, ui: &mut egui::Ui) {
+    let name = displayed_name(process_info, user_input);
+    if !user_input.hierarchical_view() {
+        body_cell(name, ui);
+        return;
+    }
+
+    ui.style_mut().interaction.selectable_labels = false;
+    ui.horizontal_centered(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        ui.label(egui::RichText::new(tree_guides_text(&process_info.tree_guides)).monospace());
+
+        if process_info.has_children {
+            let symbol = if process_info.collapsed {
+                COLLAPSED_SYMBOL
+            } else {
+                EXPANDED_SYMBOL
+            };
+            let toggle = egui::Button::new(symbol).small().frame(false);
+            if ui
+                .add_sized([TREE_TOGGLE_WIDTH, ROW_HEIGHT], toggle)
+                .clicked()
+            {
+                user_input.set_collapsed(process_info.key(), !process_info.collapsed);
+            }
+        } else {
+            ui.add_space(TREE_TOGGLE_WIDTH);
+        }
+
+        ui.label(name);
+    });
+}
+
+fn displayed_usage(
+    process_info: &data::ProcessInfo,
+    user_input: &UserInput,
+) -> (data::ResourceUsage, &'static str) {
+    let show_subtree_usage = user_input.hierarchical_view()
+        && process_info.has_children
+        && (process_info.collapsed || user_input.show_subtree_totals());
+
+    match process_info.subtree_usage {
+        Some(subtree_usage) if show_subtree_usage => (subtree_usage, SUBTREE_TOTAL_SYMBOL),
+        _ => (process_info.resource_usage(), ""),
+    }
+}
+
 fn format_bytes(bytes: u64) -> String {
     bytesize::ByteSize(bytes).to_string()
 }
 
+fn format_breakdown(
+    breakdown: Option<data::MemoryBreakdown>,
+    value: impl Fn(data::MemoryBreakdown) -> u64,
+) -> String {
+    breakdown.map_or_else(
+        || UNKNOWN_VALUE.to_owned(),
+        |breakdown| format_bytes(value(breakdown)),
+    )
+}
+
+fn format_cpu(cpu: f32) -> String {
+    format!("{cpu:.2}%")
+}
+
+fn format_timestamp(seconds_since_epoch: u64) -> String {
+    i64::try_from(seconds_since_epoch)
+        .ok()
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+        .map_or_else(
+            || UNKNOWN_VALUE.to_owned(),
+            |time| {
+                time.with_timezone(&chrono::Local)
+                    .format(TIMESTAMP_FORMAT)
+                    .to_string()
+            },
+        )
+}
+
+fn format_start_time(seconds_since_epoch: u64) -> String {
+    let Some(time) = i64::try_from(seconds_since_epoch)
+        .ok()
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+    else {
+        return UNKNOWN_VALUE.to_owned();
+    };
+    let time = time.with_timezone(&chrono::Local);
+    let today = chrono::Local::now().date_naive();
+    let format = if time.date_naive() == today {
+        START_TIME_TODAY_FORMAT
+    } else if time.year() == today.year() {
+        START_TIME_THIS_YEAR_FORMAT
+    } else {
+        START_TIME_FORMAT
+    };
+    time.format(format).to_string()
+}
+
+fn format_duration(seconds: u64) -> String {
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds / 3_600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+fn format_optional(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| UNKNOWN_VALUE.to_owned(), |value| value.to_string())
+}
+
+fn format_status(status: sysinfo::ProcessStatus) -> String {
+    if status == sysinfo::ProcessStatus::Stop {
+        format!("{PAUSE_SYMBOL} {status}")
+    } else {
+        status.to_string()
+    }
+}
+
+fn column_visible(category: data::SortCategory, user_input: &UserInput) -> bool {
+    !OPTIONAL_COLUMNS.contains(&category) || user_input.shows_optional_column(category)
+}
+
+fn visible_columns(user_input: &UserInput) -> Vec<(&'static str, data::SortCategory)> {
+    HEADER_COLUMNS
+        .into_iter()
+        .filter(|&(_, category)| column_visible(category, user_input))
+        .collect()
+}
+
+fn table_column(category: data::SortCategory) -> egui_extras::Column {
+    match category {
+        data::SortCategory::Name | data::SortCategory::Path | data::SortCategory::Command => {
+            large_column()
+        }
+        _ => small_column(),
+    }
+}
+
 fn large_column() -> egui_extras::Column {
     egui_extras::Column::exact(LARGE_COLUMN_WIDTH)
         .clip(true)
@@ -236,94 +570,269 @@ fn small_column() -> egui_extras::Column {
         .resizable(true)
 }
 
+fn body_row(
+    mut row: egui_extras::TableRow<'_, '_>,
+    columns: &[(&str, data::SortCategory)],
+    process_info: &data::ProcessInfo,
+    system: &sysinfo::System,
+    user_input: &mut UserInput,
+) {
+    row.set_selected(user_input.is_selected(process_info.id));
+
+    for (_, category) in columns {
+        row.col(|ui| {
+            row_text_color(process_info, ui);
+            if *category == data::SortCategory::Name {
+                name_cell(process_info, user_input, ui);
+            } else {
+                body_cell(&cell_text(*category, process_info, user_input), ui);
+            }
+        });
+    }
+
+    let response = row.response();
+    if response_primary_clicked(&response) {
+        let modifiers = response.ctx.input(|input| input.modifiers);
+        user_input.select(process_info.id, modifiers);
+    }
+    if response.double_clicked() {
+        details::open_details(system, process_info.id, user_input);
+    }
+    response.context_menu(|ui| {
+        actions::process_context_menu(system, process_info.id, user_input, ui);
+    });
+}
+
+fn cell_text(
+    category: data::SortCategory,
+    process_info: &data::ProcessInfo,
+    user_input: &UserInput,
+) -> String {
+    let (usage, prefix) = displayed_usage(process_info, user_input);
+    match category {
+        data::SortCategory::Name => displayed_name(process_info, user_input).to_owned(),
+        data::SortCategory::Id => process_info.id.to_string(),
+        data::SortCategory::ParentId => format_optional(process_info.parent_id),
+        data::SortCategory::User => process_info.user.clone(),
+        data::SortCategory::Memory => format!("{prefix}{}", format_bytes(usage.memory)),
+        data::SortCategory::Cpu => format!("{prefix}{}", format_cpu(usage.cpu)),
+        data::SortCategory::Nice => format_optional(process_info.nice),
+        data::SortCategory::Threads => format_optional(process_info.threads),
+        data::SortCategory::DiskRead => format!("{prefix}{}", format_bytes(usage.disk_read)),
+        data::SortCategory::DiskWrite => format!("{prefix}{}", format_bytes(usage.disk_write)),
+        data::SortCategory::Path => process_info.path.clone(),
+        data::SortCategory::Status => format_status(process_info.status),
+        data::SortCategory::StartTime => format_start_time(process_info.start_time),
+        data::SortCategory::RunTime => format_duration(process_info.run_time),
+        data::SortCategory::Session => format_optional(process_info.session_id),
+        data::SortCategory::ProcessGroup => format_optional(process_info.process_group),
+        data::SortCategory::Tty => format_optional(process_info.tty.as_deref()),
+        data::SortCategory::Command => process_info.command.clone(),
+        data::SortCategory::VirtualMemory => format_bytes(process_info.virtual_memory),
+        data::SortCategory::SharedMemory => {
+            format_breakdown(process_info.memory_breakdown, |breakdown| breakdown.shared)
+        }
+        data::SortCategory::PrivateMemory => {
+            format_breakdown(process_info.memory_breakdown, |breakdown| breakdown.private)
+        }
+        data::SortCategory::ProportionalMemory if !prefix.is_empty() => {
+            let partial = if usage.unknown_proportional_memory > 0 {
+                PARTIAL_TOTAL_SYMBOL
+            } else {
+                ""
+            };
+            format!(
+                "{prefix}{partial}{}",
+                format_bytes(usage.proportional_memory)
+            )
+        }
+        data::SortCategory::ProportionalMemory => {
+            format_breakdown(process_info.memory_breakdown, |breakdown| {
+                breakdown.proportional
+            })
+        }
+        data::SortCategory::Swap => {
+            format_breakdown(process_info.memory_breakdown, |breakdown| breakdown.swap)
+        }
+    }
+}
+
+fn column_filter_cell(
+    column: data::SortCategory,
+    known_users: &BTreeSet<String>,
+    user_input: &mut UserInput,
+    ui: &mut egui::Ui,
+) {
+    let filter_options = user_input.filter_options();
+    let filter_error = user_input
+        .column_filters()
+        .column_error(column, filter_options);
+    let column_filters = user_input.column_filters_mut();
+
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 2.0;
+
+        match column {
+            data::SortCategory::Status => {
+                status_filter_menu(&mut column_filters.statuses, ui);
+            }
+            data::SortCategory::User => {
+                user_filter_menu(&mut column_filters.users, known_users, ui);
+            }
+            _ => {}
+        }
+
+        let Some(text) = column_filters.text_mut(column) else {
+            return;
+        };
+        let mut text_edit = egui::TextEdit::singleline(text)
+            .hint_text(column_filter_hint(column))
+            .desired_width(f32::INFINITY);
+        if filter_error.is_some() {
+            text_edit = text_edit.text_color(ui.visuals().error_fg_color);
+        }
+        let response = ui.add(text_edit);
+        if let Some(error) = filter_error {
+            response.on_hover_text(error.to_string());
+        }
+    });
+}
+
+fn column_filter_hint(column: data::SortCategory) -> &'static str {
+    match column {
+        data::SortCategory::Id
+        | data::SortCategory::ParentId
+        | data::SortCategory::Session
+        | data::SortCategory::ProcessGroup => "1,5,10..20",
+        data::SortCategory::Threads => ">10",
+        data::SortCategory::Memory
+        | data::SortCategory::VirtualMemory
+        | data::SortCategory::SharedMemory
+        | data::SortCategory::PrivateMemory
+        | data::SortCategory::ProportionalMemory
+        | data::SortCategory::Swap
+        | data::SortCategory::DiskRead
+        | data::SortCategory::DiskWrite => ">100MB",
+        data::SortCategory::Cpu => ">5",
+        data::SortCategory::Nice => "-20..0",
+        data::SortCategory::StartTime => ">08:00",
+        data::SortCategory::RunTime => "<5m",
+        data::SortCategory::Name
+        | data::SortCategory::User
+        | data::SortCategory::Path
+        | data::SortCategory::Status
+        | data::SortCategory::Tty
+        | data::SortCategory::Command => "",
+    }
+}
+
+fn status_filter_menu(statuses: &mut BTreeSet<String>, ui: &mut egui::Ui) {
+    let title = filter_menu_title(statuses.len());
+    ui.menu_button(title, |ui| {
+        for status in data::STATUS_FILTER_OPTIONS {
+            set_membership_checkbox(statuses, status.to_string(), &format_status(status), ui);
+        }
+    });
+}
+
+fn user_filter_menu(
+    users: &mut BTreeSet<String>,
+    known_users: &BTreeSet<String>,
+    ui: &mut egui::Ui,
+) {
+    let title = filter_menu_title(users.len());
+    ui.menu_button(title, |ui| {
+        for user in known_users.union(&users.clone()) {
+            set_membership_checkbox(users, user.clone(), user, ui);
+        }
+    });
+}
+
+fn filter_menu_title(selected_count: usize) -> String {
+    if selected_count == 0 {
+        FILTER_MENU_SYMBOL.to_owned()
+    } else {
+        format!("{FILTER_MENU_SYMBOL}{selected_count}")
+    }
+}
+
+fn set_membership_checkbox(
+    set: &mut BTreeSet<String>,
+    value: String,
+    label: &str,
+    ui: &mut egui::Ui,
+) {
+    let mut checked = set.contains(&value);
+    if ui.checkbox(&mut checked, label).changed() {
+        if checked {
+            set.insert(value);
+        } else {
+            set.remove(&value);
+        }
+    }
+}
+
 fn update_table(app: &app::App, ui: &mut egui::Ui) {
     let processes_info = data::prepare_processes(app);
-    let user_input = app.user_input();
-    let Ok(mut user_input) = user_input.write() else {
+    let show_column_filters = app
+        .user_input()
+        .read()
+        .is_ok_and(|user_input| user_input.show_column_filters());
+    let known_users = if show_column_filters {
+        data::running_users(app)
+    } else {
+        BTreeSet::new()
+    };
+    let (system, user_input) = (app.system(), app.user_input());
+    let (Ok(system), Ok(mut user_input)) = (system.read(), user_input.write()) else {
         return;
     };
+    user_input.set_visible_pids(
+        processes_info
+            .iter()
+            .map(|process_info| process_info.id)
+            .collect(),
+    );
+    let enter_pressed = ui.input(|input| input.key_pressed(egui::Key::Enter))
+        && ui.memory(|memory| memory.focused().is_none());
+    if enter_pressed && let Some(pid) = user_input.selected_pid() {
+        details::open_details(&system, pid, &mut user_input);
+    }
+    let header_height = if show_column_filters {
+        HEADER_HEIGHT + COLUMN_FILTER_HEIGHT
+    } else {
+        HEADER_HEIGHT
+    };
+
+    let columns = visible_columns(&user_input);
+    let optional_columns = OPTIONAL_COLUMNS.map(|category| column_visible(category, &user_input));
+    let mut table = egui_extras::TableBuilder::new(ui)
+        .id_salt(optional_columns)
+        .striped(true);
+    for &(_, category) in &columns {
+        table = table.column(table_column(category));
+    }
 
-    egui_extras::TableBuilder::new(ui)
-        .striped(true)
-        .column(large_column())
-        .columns(small_column(), 6)
-        .column(large_column())
-        .column(small_column())
-        .header(HEADER_HEIGHT, |mut header_row| {
-            let sorting = !user_input.hierarchical_view();
-            let sort_method = user_input.sort_method_mut();
-            header_row.col(|ui| header_cell("Name", None, sorting, sort_method, ui));
-            header_row.col(|ui| {
-                header_cell("ID", Some(data::SortCategory::Id), sorting, sort_method, ui);
-            });
-            header_row.col(|ui| header_cell("User", None, sorting, sort_method, ui));
-            header_row.col(|ui| {
-                header_cell(
-                    "Memory",
-                    Some(data::SortCategory::Memory),
-                    sorting,
-                    sort_method,
-                    ui,
-                );
-            });
-            header_row.col(|ui| {
-                header_cell(
-                    "CPU",
-                    Some(data::SortCategory::Cpu),
-                    sorting,
-                    sort_method,
-                    ui,
-                );
-            });
-            header_row.col(|ui| {
-                header_cell(
-                    "Disk Read",
-                    Some(data::SortCategory::DiskRead),
-                    sorting,
-                    sort_method,
-                    ui,
-                );
-            });
-            header_row.col(|ui| {
-                header_cell(
-                    "Disk Write",
-                    Some(data::SortCategory::DiskWrite),
-                    sorting,
-                    sort_method,
-                    ui,
-                );
-            });
-            header_row.col(|ui| header_cell("Path", None, sorting, sort_method, ui));
-            header_row.col(|ui| header_cell("Status", None, sorting, sort_method, ui));
+    table
+        .header(header_height, |mut header_row| {
+            for &(text, sort_category) in &columns {
+                header_row.col(|ui| {
+                    if !show_column_filters {
+                        header_cell(text, Some(sort_category), user_input.sort_method_mut(), ui);
+                        return;
+                    }
+                    let header_size = egui::vec2(ui.available_width(), HEADER_HEIGHT);
+                    ui.allocate_ui(header_size, |ui| {
+                        header_cell(text, Some(sort_category), user_input.sort_method_mut(), ui);
+                    });
+                    column_filter_cell(sort_category, &known_users, &mut user_input, ui);
+                });
+            }
         })
         .body(|mut body_rows| {
             for process_info in processes_info {
-                body_rows.row(ROW_HEIGHT, |mut row| {
-                    row.set_selected(user_input.selected_pid() == Some(process_info.id));
-
-                    row.col(|ui| {
-                        let name = &mut process_info.name.clone();
-                        if process_info.child_depth > 0 {
-                            name.insert_str(0, CHILD_POINT_SYMBOL);
-                            name.insert_str(
-                                0,
-                                &CHILD_SPACE_SYMBOL.repeat(process_info.child_depth - 1),
-                            );
-                        }
-                        body_cell(name, ui);
-                    });
-                    row.col(|ui| body_cell(process_info.id.to_string().as_str(), ui));
-                    row.col(|ui| body_cell(&process_info.user, ui));
-                    row.col(|ui| body_cell(format_bytes(process_info.memory).as_str(), ui));
-                    row.col(|ui| body_cell(&process_info.cpu, ui));
-                    row.col(|ui| body_cell(format_bytes(process_info.disk_read).as_str(), ui));
-                    row.col(|ui| body_cell(format_bytes(process_info.disk_write).as_str(), ui));
-                    row.col(|ui| body_cell(&process_info.path, ui));
-                    row.col(|ui| body_cell(&process_info.status, ui));
-
-                    if response_primary_clicked(&row.response()) {
-                        user_input.set_selected_pid(Some(process_info.id));
-                    }
+                body_rows.row(ROW_HEIGHT, |row| {
+                    body_row(row, &columns, &process_info, &system, &mut user_input);
                 });
             }
         });
@@ -0,0 +1,45 @@
+use crate::processes;
+use std::path::Path;
+use std::{fmt, fs, io};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(default)]
+pub struct FilterPreset {
+    pub(crate) name: String,
+    pub(crate) process_filter: String,
+    pub(crate) filter_options: processes::FilterOptions,
+    pub(crate) show_column_filters: bool,
+    pub(crate) column_filters: processes::ColumnFilters,
+    pub(crate) include_filter_descendants: bool,
+    pub(crate) sort_method: processes::SortMethod,
+    pub(crate) show_thread_processes: bool,
+    pub(crate) hierarchical_view: bool,
+}
+
+pub enum PresetFileError {
+    Io(io::Error),
+    Format(String),
+}
+
+impl fmt::Display for PresetFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Format(error) => write!(f, "invalid preset file: {error}"),
+        }
+    }
+}
+
+pub fn export_presets(path: &str, presets: &[FilterPreset]) -> Result<(), PresetFileError> {
+    let contents = ron::ser::to_string_pretty(presets, ron::ser::PrettyConfig::default())
+        .map_err(|error| PresetFileError::Format(error.to_string()))?;
+    if let Some(directory) = Path::new(path).parent() {
+        fs::create_dir_all(directory).map_err(PresetFileError::Io)?;
+    }
+    fs::write(path, contents).map_err(PresetFileError::Io)
+}
+
+pub fn import_presets(path: &str) -> Result<Vec<FilterPreset>, PresetFileError> {
+    let contents = fs::read_to_string(path).map_err(PresetFileError::Io)?;
+    ron::from_str(&contents).map_err(|error| PresetFileError::Format(error.to_string()))
+}